@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pgident::{PgIdent, PgName};
+
+fn two_parts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("new_ns_two_parts");
+    group.bench_function("new_ns", |b| {
+        b.iter(|| PgName::new_ns(black_box(vec!["public", "users"])).unwrap())
+    });
+    // The previous implementation, for comparison
+    group.bench_function("remove_baseline", |b| {
+        b.iter(|| {
+            let mut ids = vec![
+                PgIdent::new(black_box("public")).unwrap(),
+                PgIdent::new(black_box("users")).unwrap(),
+            ];
+            PgName::IdPair(ids.remove(0), ids.remove(0))
+        })
+    });
+    group.bench_function("into_iter", |b| {
+        b.iter(|| {
+            let ids = vec![
+                PgIdent::new(black_box("public")).unwrap(),
+                PgIdent::new(black_box("users")).unwrap(),
+            ];
+            let mut it = ids.into_iter();
+            PgName::IdPair(it.next().unwrap(), it.next().unwrap())
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, two_parts);
+criterion_main!(benches);
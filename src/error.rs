@@ -6,4 +6,38 @@ pub enum IdentError {
     NullByteError(),
     #[error("Zero length identifier")]
     ZeroLengthError(),
+    #[error("Identifier is {bytes} bytes, longer than Postgres allows")]
+    TooLong { bytes: usize },
+    #[error("Empty name part at index {index}")]
+    EmptySegment { index: usize },
+    #[error("Control character {0:?} in identifier")]
+    ControlCharError(char),
+    #[error("Character {ch:?} at byte {offset} is not allowed in identifier")]
+    DisallowedCharacter { ch: char, offset: usize },
+    #[error("Identifier must be quoted")]
+    QuotingRequiredError(),
+    #[error("Unterminated quoted identifier")]
+    UnterminatedQuoteError(),
+    #[error("Unexpected character {ch:?} at byte {offset}")]
+    UnexpectedCharError { ch: char, offset: usize },
+    #[error("Invisible character {ch:?} at byte {offset} in identifier")]
+    InvisibleCharacter { ch: char, offset: usize },
+    #[error("Unicode noncharacter {ch:?} in identifier")]
+    InvalidUnicode { ch: char },
+    #[error("Identifier does not match the pattern {pattern}")]
+    PatternMismatch { pattern: String },
+    #[error("Reserved key word {word:?} at byte {offset} must be quoted")]
+    ReservedKeyword { word: String, offset: usize },
+    #[error("Identifier starts with the reserved system prefix pg_")]
+    ReservedSystemPrefix(),
+    #[error("Rendered name {0:?} does not parse back to the same name")]
+    UnsafeRenderingError(String),
+    #[error("Formatting error")]
+    FormatError(#[from] std::fmt::Error),
+    #[error("Unsupported path component {0:?}")]
+    PathComponentError(String),
+    #[error("Expected {expected} name parts, got {actual}")]
+    PartCountError { expected: usize, actual: usize },
+    #[error("Empty {0} list")]
+    EmptyListError(&'static str),
 }
\ No newline at end of file
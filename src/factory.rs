@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use crate::{IdentError, PgIdent, PgName};
+
+// Builds many names in the same schema, validating the schema once and sharing
+// its storage between all the names produced.
+#[derive(Debug, Clone)]
+pub struct NameFactory {
+    pub schema: PgIdent<Arc<str>>,
+}
+
+impl NameFactory {
+    pub fn new(schema: &str) -> Result<Self, IdentError> {
+        Ok(Self { schema: PgIdent::new(Arc::from(schema))? })
+    }
+
+    pub fn relation(&self, name: &str) -> Result<PgName<Arc<str>>, IdentError> {
+        Ok(PgName::IdPair(self.schema.clone(), PgIdent::new(Arc::from(name))?))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_schema() {
+        let factory = NameFactory::new("app").unwrap();
+        let names: Vec<_> = ["users", "Orders", "events"]
+            .iter()
+            .map(|name| factory.relation(name).unwrap())
+            .collect();
+        let rendered: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        assert_eq!(rendered, vec!["app.users", "app.\"Orders\"", "app.events"]);
+
+        let PgIdent::Id(shared) = &factory.schema else { panic!("schema should be bare") };
+        for name in &names {
+            let PgName::IdPair(PgIdent::Id(schema), _) = name else { panic!("expected a pair") };
+            assert!(Arc::ptr_eq(schema, shared));
+        }
+    }
+
+    #[test]
+    fn invalid_relation() {
+        let factory = NameFactory::new("app").unwrap();
+        assert!(matches!(factory.relation(""), Err(IdentError::ZeroLengthError())));
+        assert!(NameFactory::new("a\x00").is_err());
+    }
+
+}
@@ -1,7 +1,67 @@
 use crate::IdentError;
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
-use crate::util::is_ident_compatible;
+use crate::keywords::keyword_category;
+use crate::parse::Parser;
+
+use crate::util::{clip, escape, fnv1a, fold, is_ident_char, is_ident_compatible, is_ident_start, quote, unescape, MAX_IDENT_BYTES};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Charset {
+    // Anything Postgres accepts (quoting where needed)
+    #[default]
+    Postgres,
+    // Only [a-z0-9_], for identifiers that must be portable
+    Strict,
+    Custom(fn(char) -> bool),
+}
+
+impl Charset {
+    pub fn allows(&self, c: char) -> bool {
+        match self {
+            Self::Postgres => true,
+            Self::Strict => c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_',
+            Self::Custom(allowed) => allowed(c),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseMode {
+    // Lower-case ASCII letters, as Postgres does for unquoted names, so `Foo` becomes `foo`
+    Fold,
+    // Keep the case as given, quoting where needed, so `Foo` becomes `"Foo"`
+    Preserve,
+}
+
+// Why an identifier has to be quoted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteReason {
+    // A reserved key word, e.g. `select`
+    Keyword,
+    // Upper-case letters, which would be folded if bare, e.g. `Users`
+    Case,
+    // Starts with something other than a letter or underscore, e.g. `1st`
+    LeadingCharacter,
+    // Characters that can't appear in a bare name, e.g. spaces or `-`
+    Character,
+    // Would be valid bare, but was quoted anyway, e.g. parsed from `"users"`
+    Explicit,
+}
+
+// A change made by `PgIdent::new_or_sanitized` to make a name valid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjustment {
+    RemovedNullBytes,
+    // Clipped from `bytes` long to the maximum length
+    Truncated { bytes: usize },
+    // Nothing was left, so `_` was used instead
+    ReplacedEmpty,
+}
 
 #[derive(Debug)]
 pub enum PgIdent<T> where T: AsRef<str> {
@@ -11,6 +71,18 @@ pub enum PgIdent<T> where T: AsRef<str> {
 
 impl<T: AsRef<str>> PgIdent<T> {
     pub fn new(id: T) -> Result<Self, IdentError> {
+        Self::new_with_limit(id, MAX_IDENT_BYTES)
+    }
+
+    // For servers built with a non-default NAMEDATALEN, `max_bytes` should be NAMEDATALEN - 1.
+    // Names longer than that are rejected, rather than letting Postgres silently truncate them.
+    pub fn new_with_limit(id: T, max_bytes: usize) -> Result<Self, IdentError> {
+        if id.as_ref().is_empty() {
+            return Err(IdentError::ZeroLengthError());
+        }
+        if id.as_ref().len() > max_bytes {
+            return Err(IdentError::TooLong { bytes: id.as_ref().len() });
+        }
         if is_ident_compatible(id.as_ref()) {
             Ok(Self::Id(id))
         } else {
@@ -18,8 +90,282 @@ impl<T: AsRef<str>> PgIdent<T> {
             if id.contains('\x00') {
                 return Err(IdentError::NullByteError{});
             }
-            Ok(Self::Quoted(id.replace("\"", "\"\"")))
+            Ok(Self::Quoted(escape(id)))
+        }
+    }
+
+    // Truncates the name to the length Postgres would use, on a character boundary.
+    // The flag is true when the name had to be truncated.
+    pub fn new_truncating(id: T) -> Result<(PgIdent<String>, bool), IdentError> {
+        let id = id.as_ref();
+        let truncated = clip(id, MAX_IDENT_BYTES);
+        Ok((PgIdent::new(truncated.to_string())?, truncated.len() < id.len()))
+    }
+
+    // Never fails: NUL bytes are removed, long names truncated and empty names replaced,
+    // with each change listed in the order it was made.
+    pub fn new_or_sanitized(id: T) -> (PgIdent<String>, Vec<Adjustment>) {
+        let mut adjustments = Vec::new();
+        let mut id = id.as_ref().to_string();
+        if id.contains('\x00') {
+            id.retain(|c| c != '\x00');
+            adjustments.push(Adjustment::RemovedNullBytes);
+        }
+        if id.len() > MAX_IDENT_BYTES {
+            adjustments.push(Adjustment::Truncated { bytes: id.len() });
+            id.truncate(clip(&id, MAX_IDENT_BYTES).len());
+        }
+        if id.is_empty() {
+            adjustments.push(Adjustment::ReplacedEmpty);
+            id.push('_');
+        }
+        (PgIdent::from_trusted(id), adjustments)
+    }
+
+    // Folds ASCII upper-case letters the way Postgres treats unquoted names, so `Users` becomes
+    // the bare `users`, quoting the folded name if it's still not valid bare (e.g. `my table`).
+    // Names with non-ASCII upper-case letters are quoted as given, rather than guessing at
+    // locale-specific folding rules (e.g. Turkish dotted I, or ß).
+    pub fn new_folded(id: T) -> Result<PgIdent<String>, IdentError> {
+        let id = id.as_ref();
+        if id.chars().any(|c| !c.is_ascii() && c.is_uppercase()) {
+            return PgIdent::new(id.to_string());
+        }
+        PgIdent::new(fold(id).into_owned())
+    }
+
+    pub fn new_with_case_mode(id: T, mode: CaseMode) -> Result<PgIdent<String>, IdentError> {
+        match mode {
+            CaseMode::Fold => PgIdent::new_folded(id),
+            CaseMode::Preserve => PgIdent::new(id.as_ref().to_string()),
+        }
+    }
+
+    // Always quotes, so the name is used exactly as given, even when it would be valid bare
+    pub fn new_preserved(id: T) -> Result<Self, IdentError> {
+        match Self::new(id)? {
+            Self::Id(id) => Ok(Self::Quoted(escape(id.as_ref()))),
+            quoted => Ok(quoted),
+        }
+    }
+
+    pub fn new_with_charset(id: T, charset: Charset) -> Result<Self, IdentError> {
+        if let Some((offset, ch)) = id.as_ref().char_indices().find(|(_, c)| !charset.allows(*c)) {
+            return Err(IdentError::DisallowedCharacter { ch, offset });
+        }
+        Self::new(id)
+    }
+
+    // Like `new`, but rejects Unicode noncharacters (U+FDD0..U+FDEF, and U+FFFE/U+FFFF in
+    // every plane), which are valid in a Rust string but may be refused by the server.
+    pub fn new_strict(id: T) -> Result<Self, IdentError> {
+        let nonchar = |c: char| matches!(c, '\u{FDD0}'..='\u{FDEF}') || (c as u32) & 0xFFFE == 0xFFFE;
+        if let Some(ch) = id.as_ref().chars().find(|c| nonchar(*c)) {
+            return Err(IdentError::InvalidUnicode { ch });
+        }
+        Self::new(id)
+    }
+
+    // For enforcing a naming policy, e.g. `^[a-z][a-z0-9_]{0,29}$`, on top of the usual checks.
+    // The pattern is matched against the name as given.
+    #[cfg(feature = "regex")]
+    pub fn new_matching(id: T, re: &regex::Regex) -> Result<PgIdent<String>, IdentError> {
+        if !re.is_match(id.as_ref()) {
+            return Err(IdentError::PatternMismatch { pattern: re.as_str().to_string() });
+        }
+        PgIdent::new(id.as_ref().to_string())
+    }
+
+    // Like `new`, but rejects names that could collide with current or future system catalogs.
+    pub fn new_user_safe(id: T) -> Result<Self, IdentError> {
+        if fold(id.as_ref()).starts_with("pg_") {
+            return Err(IdentError::ReservedSystemPrefix());
+        }
+        Self::new(id)
+    }
+
+    // For values that are already known to be valid (e.g. built from other idents)
+    pub(crate) fn from_trusted(id: T) -> Self {
+        if is_ident_compatible(id.as_ref()) {
+            Self::Id(id)
+        } else {
+            Self::Quoted(escape(id.as_ref()))
+        }
+    }
+
+    // The identifier as given to `new`, without quoting or escaping
+    pub fn as_value(&self) -> Cow<'_, str> {
+        match self {
+            Self::Id(id) => Cow::Borrowed(id.as_ref()),
+            Self::Quoted(id) => unescape(id),
+        }
+    }
+
+    // None for bare identifiers, otherwise the first reason that applies
+    pub fn classify(&self) -> Option<QuoteReason> {
+        let Self::Quoted(_) = self else { return None };
+        let value = self.as_value();
+        if is_ident_compatible(&value) {
+            return Some(QuoteReason::Explicit);
+        }
+        if !value.starts_with(is_ident_start) {
+            return Some(QuoteReason::LeadingCharacter);
+        }
+        // Letters without case (e.g. CJK) aren't accepted bare, see `is_ident_compatible`
+        let has_case = |c: char| !c.is_alphabetic() || c.is_lowercase() || c.is_uppercase();
+        if !value.chars().all(|c| is_ident_char(c) && has_case(c)) {
+            return Some(QuoteReason::Character);
+        }
+        if value.chars().any(char::is_uppercase) {
+            return Some(QuoteReason::Case);
+        }
+        // Otherwise a valid lower-case name, so it must be a key word
+        Some(QuoteReason::Keyword)
+    }
+
+    // The value Postgres uses to identify the object: bare identifiers are folded to
+    // lower-case, quoted identifiers are taken verbatim.
+    pub(crate) fn folded(&self) -> Cow<'_, str> {
+        match self {
+            Self::Id(id) => fold(id.as_ref()),
+            Self::Quoted(id) => unescape(id),
+        }
+    }
+
+    // The characters of `folded`, without allocating
+    fn folded_chars(&self) -> impl Iterator<Item = char> + '_ {
+        let (id, quoted) = match self {
+            Self::Id(id) => (id.as_ref(), false),
+            Self::Quoted(id) => (id.as_str(), true),
+        };
+        // Quoted text holds each `"` doubled, so every second one is dropped
+        let mut after_quote = false;
+        id.chars().filter_map(move |c| {
+            if !quoted {
+                return Some(c.to_ascii_lowercase());
+            }
+            if c == '"' {
+                after_quote = !after_quote;
+                if !after_quote {
+                    return None;
+                }
+            }
+            Some(c)
+        })
+    }
+
+    pub fn rendered_len(&self) -> usize {
+        match self {
+            Self::Id(id) => id.as_ref().len(),
+            Self::Quoted(id) => id.len() + 2,
+        }
+    }
+
+    // True when both denote the same object, regardless of how they're quoted,
+    // so `"users"` matches `users`, but `"Users"` does not.
+    pub fn eq_ignore_quoting<U: AsRef<str>>(&self, other: &PgIdent<U>) -> bool {
+        self.folded() == other.folded()
+    }
+
+    // Flags byte order marks and zero-width characters, which Postgres keeps as part of the
+    // name but which are easy to copy-paste by accident and impossible to see.
+    pub fn check_invisible(&self) -> Result<(), IdentError> {
+        let invisible = |c: char| matches!(c, '\u{FEFF}' | '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{00AD}');
+        match self.as_value().char_indices().find(|(_, c)| invisible(*c)) {
+            Some((offset, ch)) => Err(IdentError::InvisibleCharacter { ch, offset }),
+            None => Ok(()),
+        }
+    }
+
+    // Compares the rendered form against raw bytes without building a String
+    pub fn rendered_bytes_eq(&self, other: &[u8]) -> bool {
+        match self {
+            Self::Id(id) => id.as_ref().as_bytes() == other,
+            Self::Quoted(id) => {
+                other.len() == id.len() + 2
+                    && other[0] == b'"'
+                    && other[other.len() - 1] == b'"'
+                    && &other[1..other.len() - 1] == id.as_bytes()
+            }
+        }
+    }
+
+    pub fn logical_len_bytes(&self) -> usize {
+        self.as_value().len()
+    }
+
+    pub fn logical_len_chars(&self) -> usize {
+        self.as_value().chars().count()
+    }
+
+    pub(crate) fn tool_canonical(&self) -> String {
+        let value = self.as_value();
+        let folded = fold(&value);
+        if is_ident_compatible(&folded) {
+            folded.into_owned()
+        } else {
+            quote(&value.to_ascii_uppercase())
+        }
+    }
+}
+
+impl PgIdent<&'static str> {
+    // A byte-for-byte comparison of bare identifiers that can be used in `const` contexts.
+    // Quoted identifiers never compare equal here, use `==` for those.
+    pub const fn const_eq(&self, other: &Self) -> bool {
+        let (Self::Id(a), Self::Id(b)) = (self, other) else { return false };
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < a.len() {
+            if a[i] != b[i] {
+                return false;
+            }
+            i += 1;
         }
+        true
+    }
+}
+
+impl PgIdent<String> {
+    // Numbered names such as `col0`, `col1`, validated as a whole
+    pub fn from_indexed(prefix: &str, n: usize) -> Result<Self, IdentError> {
+        PgIdent::new(format!("{}{}", prefix, n))
+    }
+
+    // Appends `_` and 8 hex digits derived from `base` and `seed`, e.g. `tmp_import_ff740c93`,
+    // clipping `base` so the result fits in 63 bytes. The same inputs always give the same name.
+    pub fn with_unique_suffix(base: &str, seed: u64) -> Self {
+        let base = base.replace('\x00', "");
+        let mut input = seed.to_le_bytes().to_vec();
+        input.extend_from_slice(base.as_bytes());
+        let suffix = format!("_{:08x}", fnv1a(&input) as u32);
+        let name = format!("{}{}", clip(&base, MAX_IDENT_BYTES - suffix.len()), suffix);
+        PgIdent::from_trusted(name)
+    }
+
+    pub fn from_char(c: char) -> Result<Self, IdentError> {
+        if c.is_control() && c != '\x00' {
+            return Err(IdentError::ControlCharError(c));
+        }
+        PgIdent::new(c.to_string())
+    }
+}
+
+// Matches Postgres' own `quote_ident()` exactly: only `[a-z_][a-z0-9_]*` names that aren't
+// keywords (other than unreserved ones) are left bare. This is stricter than `PgIdent::new`,
+// which also leaves `$` and non-ASCII lower-case letters unquoted.
+pub fn quote_ident_exact(id: &str) -> String {
+    let mut bytes = id.bytes();
+    let safe = matches!(bytes.next(), Some(b'a'..=b'z' | b'_'))
+        && bytes.all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'_'))
+        && keyword_category(id).is_none();
+    if safe {
+        id.to_string()
+    } else {
+        quote(id)
     }
 }
 
@@ -32,6 +378,19 @@ impl<T> Display for PgIdent<T> where T: AsRef<str> {
     }
 }
 
+impl Display for QuoteReason {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        let reason = match self {
+            Self::Keyword => "keyword",
+            Self::Case => "case",
+            Self::LeadingCharacter => "leading character",
+            Self::Character => "character",
+            Self::Explicit => "explicit",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
 impl TryFrom<&str> for PgIdent<String> {
     type Error = IdentError;
 
@@ -40,6 +399,59 @@ impl TryFrom<&str> for PgIdent<String> {
     }
 }
 
+impl TryFrom<Cow<'_, str>> for PgIdent<String> {
+    type Error = IdentError;
+
+    fn try_from(value: Cow<'_, str>) -> Result<Self, Self::Error> {
+        PgIdent::new(value.into_owned())
+    }
+}
+
+// Parses a single rendered identifier, e.g. `foo` or `"Foo"`
+impl FromStr for PgIdent<String> {
+    type Err = IdentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let id = parser.ident()?;
+        if !parser.is_at_end() {
+            return Err(parser.unexpected());
+        }
+        Ok(id)
+    }
+}
+
+// Equality follows Postgres identity: bare identifiers are compared after folding to
+// lower-case, so `users`, `Users` (bare) and `"users"` are all equal, but `"Users"` is not.
+impl<T: AsRef<str>> PartialEq for PgIdent<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.folded() == other.folded()
+    }
+}
+
+impl<T: AsRef<str>> Eq for PgIdent<T> {}
+
+// Orders by folded value, compared by code point, so `"Users"` sorts before `users`.
+// Quoted-ness can't break ties: idents that fold to the same value are `==` (e.g. `users` and
+// `"users"`), so they compare as equal to keep `Ord` consistent with `Eq` and `Hash`.
+impl<T: AsRef<str>> Ord for PgIdent<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded_chars().cmp(other.folded_chars())
+    }
+}
+
+impl<T: AsRef<str>> PartialOrd for PgIdent<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: AsRef<str>> Hash for PgIdent<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.folded().hash(state);
+    }
+}
+
 impl<T> Clone for PgIdent<T> where T: AsRef<str> + Clone {
     fn clone(&self) -> Self {
         match self {
@@ -60,12 +472,34 @@ mod tests {
         assert_eq!(format!("{}", id), "foo");
     }
 
+    #[test]
+    fn from_cow_borrowed() {
+        let id: PgIdent<String> = Cow::Borrowed("foo").try_into().unwrap();
+        assert!(matches!(id, PgIdent::Id(_)));
+        assert_eq!(format!("{}", id), "foo");
+    }
+
+    #[test]
+    fn from_cow_owned() {
+        let id: PgIdent<String> = Cow::<str>::Owned("Foo".to_string()).try_into().unwrap();
+        assert!(matches!(id, PgIdent::Quoted(_)));
+        assert_eq!(format!("{}", id), "\"Foo\"");
+        let err = PgIdent::try_from(Cow::<str>::Owned("a\x00b".to_string()));
+        assert!(matches!(err, Err(IdentError::NullByteError())));
+    }
+
     #[test]
     fn simple() {
         let id = PgIdent::new("foo").unwrap();
         assert_eq!(format!("{}", id), "foo");
     }
 
+    #[test]
+    fn empty() {
+        assert!(matches!(PgIdent::new(""), Err(IdentError::ZeroLengthError())));
+        assert!(matches!(PgIdent::try_from(""), Err(IdentError::ZeroLengthError())));
+    }
+
     #[test]
     fn quoted() {
         let id = PgIdent::new("FOO").unwrap();
@@ -73,6 +507,19 @@ mod tests {
         assert_eq!(format!("{}", id), "\"FOO\"");
     }
 
+    #[test]
+    fn reserved_keywords() {
+        for word in ["select", "where", "user", "table", "order"] {
+            let id = PgIdent::new(word).unwrap();
+            assert!(matches!(id, PgIdent::Quoted(_)));
+            assert_eq!(format!("{}", id), format!("\"{}\"", word));
+        }
+        for word in ["name", "type", "users"] {
+            let id = PgIdent::new(word).unwrap();
+            assert!(matches!(id, PgIdent::Id(_)));
+        }
+    }
+
     #[test]
     fn quoted_escaped() {
         let id = PgIdent::new("The \"table\"").unwrap();
@@ -80,4 +527,400 @@ mod tests {
         assert_eq!(format!("{}", id), "\"The \"\"table\"\"\"");
     }
 
+    #[test]
+    fn const_eq() {
+        const USERS: PgIdent<&str> = PgIdent::Id("users");
+        const ORDERS: PgIdent<&str> = PgIdent::Id("orders");
+        const _: () = assert!(USERS.const_eq(&PgIdent::Id("users")));
+        const _: () = assert!(!USERS.const_eq(&ORDERS));
+        assert!(!PgIdent::new("Users").unwrap().const_eq(&PgIdent::new("Users").unwrap()));
+    }
+
+    #[test]
+    fn classify() {
+        assert_eq!(PgIdent::new("users").unwrap().classify(), None);
+        assert_eq!(PgIdent::new("select").unwrap().classify(), Some(QuoteReason::Keyword));
+        assert_eq!(PgIdent::new("Users").unwrap().classify(), Some(QuoteReason::Case));
+        assert_eq!(PgIdent::new("Select").unwrap().classify(), Some(QuoteReason::Case));
+        assert_eq!(PgIdent::new("1st").unwrap().classify(), Some(QuoteReason::LeadingCharacter));
+        assert_eq!(PgIdent::new("My Table").unwrap().classify(), Some(QuoteReason::Character));
+        assert_eq!(PgIdent::new("日本").unwrap().classify(), Some(QuoteReason::Character));
+        assert_eq!(PgIdent::new_preserved("users").unwrap().classify(), Some(QuoteReason::Explicit));
+        assert_eq!(PgIdent::from_str("\"users\"").unwrap().classify(), Some(QuoteReason::Explicit));
+    }
+
+    #[test]
+    fn sanitized() {
+        let (id, adjustments) = PgIdent::new_or_sanitized("us\x00ers");
+        assert_eq!(id.to_string(), "users");
+        assert_eq!(adjustments, vec![Adjustment::RemovedNullBytes]);
+
+        let (id, adjustments) = PgIdent::new_or_sanitized("A".repeat(70));
+        assert_eq!(id.to_string(), format!("\"{}\"", "A".repeat(63)));
+        assert_eq!(adjustments, vec![Adjustment::Truncated { bytes: 70 }]);
+
+        let (id, adjustments) = PgIdent::new_or_sanitized("\x00");
+        assert_eq!(id.to_string(), "_");
+        assert_eq!(adjustments, vec![Adjustment::RemovedNullBytes, Adjustment::ReplacedEmpty]);
+
+        let (id, adjustments) = PgIdent::new_or_sanitized("My Table");
+        assert_eq!(id.to_string(), "\"My Table\"");
+        assert!(adjustments.is_empty());
+    }
+
+    #[test]
+    fn ordering() {
+        let mut ids = [
+            PgIdent::new("b".to_string()).unwrap(),
+            PgIdent::new("Users".to_string()).unwrap(),
+            PgIdent::from_str("\"users\"").unwrap(),
+            PgIdent::new("a".to_string()).unwrap(),
+            PgIdent::from_str("USERS").unwrap(),
+            PgIdent::new("My Table".to_string()).unwrap(),
+            PgIdent::new("_x".to_string()).unwrap(),
+            PgIdent::new("USERS".to_string()).unwrap(),
+        ];
+        ids.sort_unstable();
+        let values: Vec<_> = ids.iter().map(|id| id.as_value().into_owned()).collect();
+        assert_eq!(values, vec!["My Table", "USERS", "Users", "_x", "a", "b", "users", "users"]);
+
+        assert_eq!(PgIdent::new("users").unwrap().cmp(&PgIdent::new_preserved("users").unwrap()), Ordering::Equal);
+        assert_eq!(PgIdent::Id("Users").cmp(&PgIdent::new("users").unwrap()), Ordering::Equal);
+        assert!(PgIdent::new("Users").unwrap() < PgIdent::new("users").unwrap());
+        assert!(PgIdent::new("users").unwrap() < PgIdent::new("usersa").unwrap());
+        assert!(PgIdent::new("a\"b").unwrap() < PgIdent::new("a#").unwrap());
+        assert_eq!(PgIdent::new("a\"\"b").unwrap().cmp(&PgIdent::new("a\"\"b").unwrap()), Ordering::Equal);
+        assert!(PgIdent::new("a\"\"b").unwrap() < PgIdent::new("a\"b").unwrap());
+    }
+
+    #[test]
+    fn strict_unicode() {
+        assert!(matches!(PgIdent::new_strict("a\u{FFFE}"), Err(IdentError::InvalidUnicode { ch: '\u{FFFE}' })));
+        assert!(matches!(PgIdent::new_strict("\u{10FFFF}"), Err(IdentError::InvalidUnicode { .. })));
+        assert!(matches!(PgIdent::new_strict("\u{FDD0}"), Err(IdentError::InvalidUnicode { .. })));
+        assert!(PgIdent::new_strict("café\u{FFFD}").is_ok());
+        assert!(PgIdent::new("a\u{FFFE}").is_ok());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn matching() {
+        let re = regex::Regex::new("^[a-z][a-z0-9_]{0,29}$").unwrap();
+        assert_eq!(PgIdent::new_matching("users_2", &re).unwrap().to_string(), "users_2");
+        assert!(matches!(PgIdent::new_matching("Users", &re), Err(IdentError::PatternMismatch { .. })));
+        assert!(matches!(PgIdent::new_matching("a".repeat(31), &re), Err(IdentError::PatternMismatch { .. })));
+        // Still subject to the usual rules
+        let re = regex::Regex::new("^.*$").unwrap();
+        assert!(matches!(PgIdent::new_matching("", &re), Err(IdentError::ZeroLengthError())));
+    }
+
+    #[test]
+    fn indexed() {
+        assert_eq!(PgIdent::from_indexed("col", 0).unwrap().to_string(), "col0");
+        assert_eq!(PgIdent::from_indexed("Col", 12).unwrap().to_string(), "\"Col12\"");
+        assert_eq!(PgIdent::from_indexed("", 1).unwrap().to_string(), "\"1\"");
+        assert!(matches!(PgIdent::from_indexed(&"c".repeat(62), 10), Err(IdentError::TooLong { bytes: 64 })));
+    }
+
+    #[test]
+    fn unique_suffix() {
+        let id = PgIdent::with_unique_suffix("tmp_import", 1);
+        assert_eq!(id.to_string(), PgIdent::with_unique_suffix("tmp_import", 1).to_string());
+        assert_ne!(id.to_string(), PgIdent::with_unique_suffix("tmp_import", 2).to_string());
+        // Pinned, as generated names must not change between releases
+        assert_eq!(id.to_string(), "tmp_import_ff740c93");
+
+        let id = PgIdent::with_unique_suffix(&"é".repeat(40), 7);
+        assert!(id.logical_len_bytes() <= 63);
+        assert!(id.as_value().starts_with("ééé"));
+        assert_eq!(id.as_value(), PgIdent::with_unique_suffix(&"é".repeat(40), 7).as_value());
+    }
+
+    #[test]
+    fn too_long() {
+        assert!(PgIdent::new("a".repeat(63)).is_ok());
+        assert!(matches!(PgIdent::new("a".repeat(64)), Err(IdentError::TooLong { bytes: 64 })));
+        // 32 characters, but 64 bytes
+        assert!(matches!(PgIdent::new("é".repeat(32)), Err(IdentError::TooLong { bytes: 64 })));
+        assert!(matches!(PgIdent::new("A".repeat(64)), Err(IdentError::TooLong { bytes: 64 })));
+        let quoted = format!("\"{}\"", "a".repeat(64));
+        assert!(matches!(PgIdent::from_str(&quoted), Err(IdentError::TooLong { bytes: 64 })));
+
+        assert!(PgIdent::new_with_limit("a".repeat(127), 127).is_ok());
+        assert!(matches!(PgIdent::new_with_limit("users", 4), Err(IdentError::TooLong { bytes: 5 })));
+    }
+
+    #[test]
+    fn truncating() {
+        let (id, truncated) = PgIdent::new_truncating("a".repeat(70)).unwrap();
+        assert!(truncated);
+        assert!(matches!(id, PgIdent::Id(_)));
+        assert_eq!(id.to_string(), "a".repeat(63));
+
+        let (id, truncated) = PgIdent::new_truncating(format!("{}é", "a".repeat(62))).unwrap();
+        assert!(truncated);
+        assert_eq!(id.to_string(), "a".repeat(62));
+
+        let (id, truncated) = PgIdent::new_truncating("users").unwrap();
+        assert!(!truncated);
+        assert_eq!(id.to_string(), "users");
+    }
+
+    #[test]
+    fn folded() {
+        let id = PgIdent::new_folded("Users").unwrap();
+        assert!(matches!(id, PgIdent::Id(_)));
+        assert_eq!(id.to_string(), "users");
+        assert_eq!(PgIdent::new_folded("STRASSE").unwrap().to_string(), "strasse");
+        assert_eq!(PgIdent::new_folded("Straße").unwrap().to_string(), "straße");
+
+        assert_eq!(PgIdent::new_folded("My Table").unwrap().to_string(), "\"my table\"");
+        assert_eq!(PgIdent::new_folded("İstanbul").unwrap().to_string(), "\"İstanbul\"");
+        assert_eq!(PgIdent::new_folded("ÄBC").unwrap().to_string(), "\"ÄBC\"");
+        assert_eq!(PgIdent::new_folded("Select").unwrap().to_string(), "\"select\"");
+        assert!(PgIdent::new_folded("").is_err());
+    }
+
+    #[test]
+    fn case_mode() {
+        let id = PgIdent::new_with_case_mode("Foo", CaseMode::Fold).unwrap();
+        assert!(matches!(id, PgIdent::Id(_)));
+        assert_eq!(id.to_string(), "foo");
+        let id = PgIdent::new_with_case_mode("Foo", CaseMode::Preserve).unwrap();
+        assert!(matches!(id, PgIdent::Quoted(_)));
+        assert_eq!(id.to_string(), "\"Foo\"");
+        let id = PgIdent::new_with_case_mode("My Table", CaseMode::Fold).unwrap();
+        assert_eq!(id.to_string(), "\"my table\"");
+    }
+
+    #[test]
+    fn preserved() {
+        let id = PgIdent::new_preserved("Foo").unwrap();
+        assert_eq!(format!("{}", id), "\"Foo\"");
+        let id = PgIdent::new_preserved("foo").unwrap();
+        assert!(matches!(id, PgIdent::Quoted(_)));
+        assert_eq!(format!("{}", id), "\"foo\"");
+        let id = PgIdent::new_preserved("a\"b").unwrap();
+        assert_eq!(format!("{}", id), "\"a\"\"b\"");
+        assert!(PgIdent::new_preserved("a\x00").is_err());
+    }
+
+    #[test]
+    fn strict_charset() {
+        assert!(matches!(
+            PgIdent::new_with_charset("a$b", Charset::Strict),
+            Err(IdentError::DisallowedCharacter { ch: '$', offset: 1 })
+        ));
+        assert!(matches!(
+            PgIdent::new_with_charset("café", Charset::Strict),
+            Err(IdentError::DisallowedCharacter { ch: 'é', offset: 3 })
+        ));
+        let id = PgIdent::new_with_charset("user_2", Charset::Strict).unwrap();
+        assert_eq!(format!("{}", id), "user_2");
+        assert!(PgIdent::new_with_charset("a$b", Charset::Postgres).is_ok());
+    }
+
+    #[test]
+    fn custom_charset() {
+        let no_digits = Charset::Custom(|c| !c.is_ascii_digit());
+        assert!(PgIdent::new_with_charset("abc", no_digits).is_ok());
+        assert!(matches!(
+            PgIdent::new_with_charset("ab1", no_digits),
+            Err(IdentError::DisallowedCharacter { ch: '1', offset: 2 })
+        ));
+    }
+
+    #[test]
+    fn user_safe() {
+        assert!(matches!(PgIdent::new_user_safe("pg_temp_1"), Err(IdentError::ReservedSystemPrefix())));
+        assert!(matches!(PgIdent::new_user_safe("PG_stats"), Err(IdentError::ReservedSystemPrefix())));
+        let id = PgIdent::new_user_safe("mytable").unwrap();
+        assert_eq!(format!("{}", id), "mytable");
+        assert!(PgIdent::new("pg_temp_1").is_ok());
+    }
+
+    #[test]
+    fn as_value() {
+        let id = PgIdent::new("The \"table\"").unwrap();
+        assert_eq!(format!("{}", id), "\"The \"\"table\"\"\"");
+        assert_eq!(id.as_value(), "The \"table\"");
+        let id = PgIdent::new("foo").unwrap();
+        assert!(matches!(id.as_value(), Cow::Borrowed("foo")));
+        let id = PgIdent::new("My Table").unwrap();
+        assert!(matches!(id.as_value(), Cow::Borrowed("My Table")));
+    }
+
+    #[test]
+    fn from_char() {
+        let id = PgIdent::from_char('a').unwrap();
+        assert!(matches!(id, PgIdent::Id(_)));
+        assert_eq!(format!("{}", id), "a");
+
+        let id = PgIdent::from_char('1').unwrap();
+        assert!(matches!(id, PgIdent::Quoted(_)));
+        assert_eq!(format!("{}", id), "\"1\"");
+
+        assert!(matches!(PgIdent::from_char('\x07'), Err(IdentError::ControlCharError('\x07'))));
+        assert!(matches!(PgIdent::from_char('\x00'), Err(IdentError::NullByteError())));
+    }
+
+    #[test]
+    fn rendered_len() {
+        for id in ["foo", "FOO", "The \"table\"", "café"] {
+            let id = PgIdent::new(id).unwrap();
+            assert_eq!(id.rendered_len(), id.to_string().len());
+        }
+    }
+
+    #[test]
+    fn eq_ignore_quoting() {
+        let bare = PgIdent::new("users").unwrap();
+        let quoted: PgIdent<&str> = PgIdent::Quoted("users".to_string());
+        assert!(!matches!(bare, PgIdent::Quoted(_)));
+        assert!(bare.eq_ignore_quoting(&quoted));
+        assert!(quoted.eq_ignore_quoting(&bare));
+
+        let upper = PgIdent::new("Users").unwrap();
+        assert!(!upper.eq_ignore_quoting(&bare));
+        assert!(upper.eq_ignore_quoting(&PgIdent::new("Users".to_string()).unwrap()));
+    }
+
+    fn strict_eq<T: AsRef<str>, U: AsRef<str>>(a: &PgIdent<T>, b: &PgIdent<U>) -> bool {
+        match (a, b) {
+            (PgIdent::Id(a), PgIdent::Id(b)) => a.as_ref() == b.as_ref(),
+            (PgIdent::Quoted(a), PgIdent::Quoted(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let id: PgIdent<String> = "foo".parse().unwrap();
+        assert!(strict_eq(&id, &PgIdent::Id("foo")));
+        let id: PgIdent<String> = "FOO".parse().unwrap();
+        assert!(strict_eq(&id, &PgIdent::Id("foo")));
+        let id: PgIdent<String> = "\"The \"\"table\"\"\"".parse().unwrap();
+        assert!(strict_eq(&id, &PgIdent::new("The \"table\"").unwrap()));
+        let id: PgIdent<String> = "\"foo\"".parse().unwrap();
+        assert!(matches!(id, PgIdent::Quoted(_)));
+
+        assert!(matches!("select".parse::<PgIdent<String>>(), Err(IdentError::ReservedKeyword { .. })));
+        assert!(matches!("TABLE".parse::<PgIdent<String>>(), Err(IdentError::ReservedKeyword { offset: 0, .. })));
+        let id: PgIdent<String> = "\"select\"".parse().unwrap();
+        assert_eq!(id.to_string(), "\"select\"");
+        // Non-reserved key words are fine bare
+        assert_eq!("name".parse::<PgIdent<String>>().unwrap().to_string(), "name");
+
+        assert!(matches!("\"foo".parse::<PgIdent<String>>(), Err(IdentError::UnterminatedQuoteError())));
+        assert!(matches!("\"\"".parse::<PgIdent<String>>(), Err(IdentError::ZeroLengthError())));
+        assert!(matches!(
+            "foo bar".parse::<PgIdent<String>>(),
+            Err(IdentError::UnexpectedCharError { ch: ' ', offset: 3 })
+        ));
+        assert!(matches!(
+            "\"a\"\"".parse::<PgIdent<String>>(),
+            Err(IdentError::UnterminatedQuoteError())
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        let cases = ["foo", "FOO", "select", "SELECT", "Foo", "a\"b", "\"", "\"\"", "a b", "café", "ß", "İ", "1a", "a$", "_", "a.b", "中"];
+        for case in cases {
+            let id = PgIdent::new(case).unwrap();
+            let parsed: PgIdent<String> = id.to_string().parse().unwrap();
+            assert!(strict_eq(&id, &parsed), "{:?} -> {} -> {:?}", case, id, parsed);
+        }
+    }
+
+    #[test]
+    fn round_trip_random() {
+        let alphabet: Vec<char> = "abcxyzABCXYZ019_$ \"'.-éßİ中".chars().collect();
+        // xorshift, so the test is deterministic without a rand dependency
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..2000 {
+            let len = 1 + (next() % 20) as usize;
+            let s: String = (0..len).map(|_| alphabet[(next() % alphabet.len() as u64) as usize]).collect();
+            let id = PgIdent::new(s.as_str()).unwrap();
+            let parsed: PgIdent<String> = id.to_string().parse().unwrap();
+            assert!(strict_eq(&id, &parsed), "{:?} -> {} -> {:?}", s, id, parsed);
+        }
+    }
+
+    #[test]
+    fn invisible_characters() {
+        let id = PgIdent::new("\u{FEFF}users").unwrap();
+        assert!(matches!(id.check_invisible(), Err(IdentError::InvisibleCharacter { ch: '\u{FEFF}', offset: 0 })));
+        let id = PgIdent::new("user\u{200D}s").unwrap();
+        assert!(matches!(id.check_invisible(), Err(IdentError::InvisibleCharacter { ch: '\u{200D}', offset: 4 })));
+        assert!(PgIdent::new("users").unwrap().check_invisible().is_ok());
+        assert!(PgIdent::new("My Table").unwrap().check_invisible().is_ok());
+    }
+
+    #[test]
+    fn rendered_bytes() {
+        let id = PgIdent::new("The \"table\"").unwrap();
+        assert!(id.rendered_bytes_eq(b"\"The \"\"table\"\"\""));
+        assert!(!id.rendered_bytes_eq(b"The \"table\""));
+        assert!(!id.rendered_bytes_eq(b"\""));
+        assert!(!id.rendered_bytes_eq(b""));
+        let id = PgIdent::new("foo").unwrap();
+        assert!(id.rendered_bytes_eq(b"foo"));
+        assert!(!id.rendered_bytes_eq(b"\"foo\""));
+    }
+
+    #[test]
+    fn quote_ident_matches_postgres() {
+        // Expected values from `SELECT quote_ident(...)` on Postgres 16
+        assert_eq!(quote_ident_exact("foo"), "foo");
+        assert_eq!(quote_ident_exact("foo_1"), "foo_1");
+        assert_eq!(quote_ident_exact("a$b"), "\"a$b\"");
+        assert_eq!(quote_ident_exact("café"), "\"café\"");
+        assert_eq!(quote_ident_exact("Foo"), "\"Foo\"");
+        assert_eq!(quote_ident_exact("select"), "\"select\"");
+        assert_eq!(quote_ident_exact("int"), "\"int\"");
+        assert_eq!(quote_ident_exact("left"), "\"left\"");
+        assert_eq!(quote_ident_exact("name"), "name");
+        assert_eq!(quote_ident_exact("a\"b"), "\"a\"\"b\"");
+        assert_eq!(quote_ident_exact(""), "\"\"");
+    }
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn postgres_equality() {
+        let bare = PgIdent::new("users").unwrap();
+        let quoted: PgIdent<&str> = PgIdent::Quoted("users".to_string());
+        let unfolded: PgIdent<&str> = PgIdent::Id("Users");
+        assert_eq!(bare, quoted);
+        assert_eq!(bare, unfolded);
+        assert_eq!(hash_of(&bare), hash_of(&quoted));
+        assert_eq!(hash_of(&bare), hash_of(&unfolded));
+
+        let upper = PgIdent::new("Users").unwrap();
+        assert_ne!(bare, upper);
+        assert_eq!(PgIdent::new("a\"b").unwrap(), PgIdent::Quoted("a\"\"b".to_string()));
+    }
+
+    #[test]
+    fn logical_len() {
+        let id = PgIdent::new("café").unwrap();
+        assert_eq!(id.logical_len_bytes(), 5);
+        assert_eq!(id.logical_len_chars(), 4);
+
+        let id = PgIdent::new("a\"b").unwrap();
+        assert_eq!(id.logical_len_bytes(), 3);
+        assert_eq!(id.logical_len_chars(), 3);
+    }
+
 }
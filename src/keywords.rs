@@ -0,0 +1,215 @@
+// Keywords from the Postgres 16 grammar (src/include/parser/kwlist.h), excluding the
+// unreserved keywords, which are always usable as bare identifiers.
+// Sorted so lookups can binary search.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordCategory {
+    // Can be used as a column name, but not as a function or type name
+    ColName,
+    // Can be used as a function or type name, but not as a column name
+    TypeFuncName,
+    Reserved,
+}
+
+const KEYWORDS: &[(&str, KeywordCategory)] = &[
+    ("all", KeywordCategory::Reserved),
+    ("analyse", KeywordCategory::Reserved),
+    ("analyze", KeywordCategory::Reserved),
+    ("and", KeywordCategory::Reserved),
+    ("any", KeywordCategory::Reserved),
+    ("array", KeywordCategory::Reserved),
+    ("as", KeywordCategory::Reserved),
+    ("asc", KeywordCategory::Reserved),
+    ("asymmetric", KeywordCategory::Reserved),
+    ("authorization", KeywordCategory::TypeFuncName),
+    ("between", KeywordCategory::ColName),
+    ("bigint", KeywordCategory::ColName),
+    ("binary", KeywordCategory::TypeFuncName),
+    ("bit", KeywordCategory::ColName),
+    ("boolean", KeywordCategory::ColName),
+    ("both", KeywordCategory::Reserved),
+    ("case", KeywordCategory::Reserved),
+    ("cast", KeywordCategory::Reserved),
+    ("char", KeywordCategory::ColName),
+    ("character", KeywordCategory::ColName),
+    ("check", KeywordCategory::Reserved),
+    ("coalesce", KeywordCategory::ColName),
+    ("collate", KeywordCategory::Reserved),
+    ("collation", KeywordCategory::TypeFuncName),
+    ("column", KeywordCategory::Reserved),
+    ("concurrently", KeywordCategory::TypeFuncName),
+    ("constraint", KeywordCategory::Reserved),
+    ("create", KeywordCategory::Reserved),
+    ("cross", KeywordCategory::TypeFuncName),
+    ("current_catalog", KeywordCategory::Reserved),
+    ("current_date", KeywordCategory::Reserved),
+    ("current_role", KeywordCategory::Reserved),
+    ("current_schema", KeywordCategory::TypeFuncName),
+    ("current_time", KeywordCategory::Reserved),
+    ("current_timestamp", KeywordCategory::Reserved),
+    ("current_user", KeywordCategory::Reserved),
+    ("dec", KeywordCategory::ColName),
+    ("decimal", KeywordCategory::ColName),
+    ("default", KeywordCategory::Reserved),
+    ("deferrable", KeywordCategory::Reserved),
+    ("desc", KeywordCategory::Reserved),
+    ("distinct", KeywordCategory::Reserved),
+    ("do", KeywordCategory::Reserved),
+    ("else", KeywordCategory::Reserved),
+    ("end", KeywordCategory::Reserved),
+    ("except", KeywordCategory::Reserved),
+    ("exists", KeywordCategory::ColName),
+    ("extract", KeywordCategory::ColName),
+    ("false", KeywordCategory::Reserved),
+    ("fetch", KeywordCategory::Reserved),
+    ("float", KeywordCategory::ColName),
+    ("for", KeywordCategory::Reserved),
+    ("foreign", KeywordCategory::Reserved),
+    ("freeze", KeywordCategory::TypeFuncName),
+    ("from", KeywordCategory::Reserved),
+    ("full", KeywordCategory::TypeFuncName),
+    ("grant", KeywordCategory::Reserved),
+    ("greatest", KeywordCategory::ColName),
+    ("group", KeywordCategory::Reserved),
+    ("grouping", KeywordCategory::ColName),
+    ("having", KeywordCategory::Reserved),
+    ("ilike", KeywordCategory::TypeFuncName),
+    ("in", KeywordCategory::Reserved),
+    ("initially", KeywordCategory::Reserved),
+    ("inner", KeywordCategory::TypeFuncName),
+    ("inout", KeywordCategory::ColName),
+    ("int", KeywordCategory::ColName),
+    ("integer", KeywordCategory::ColName),
+    ("intersect", KeywordCategory::Reserved),
+    ("interval", KeywordCategory::ColName),
+    ("into", KeywordCategory::Reserved),
+    ("is", KeywordCategory::TypeFuncName),
+    ("isnull", KeywordCategory::TypeFuncName),
+    ("join", KeywordCategory::TypeFuncName),
+    ("json", KeywordCategory::ColName),
+    ("json_array", KeywordCategory::ColName),
+    ("json_arrayagg", KeywordCategory::ColName),
+    ("json_object", KeywordCategory::ColName),
+    ("json_objectagg", KeywordCategory::ColName),
+    ("lateral", KeywordCategory::Reserved),
+    ("leading", KeywordCategory::Reserved),
+    ("least", KeywordCategory::ColName),
+    ("left", KeywordCategory::TypeFuncName),
+    ("like", KeywordCategory::TypeFuncName),
+    ("limit", KeywordCategory::Reserved),
+    ("localtime", KeywordCategory::Reserved),
+    ("localtimestamp", KeywordCategory::Reserved),
+    ("national", KeywordCategory::ColName),
+    ("natural", KeywordCategory::TypeFuncName),
+    ("nchar", KeywordCategory::ColName),
+    ("none", KeywordCategory::ColName),
+    ("normalize", KeywordCategory::ColName),
+    ("not", KeywordCategory::Reserved),
+    ("notnull", KeywordCategory::TypeFuncName),
+    ("null", KeywordCategory::Reserved),
+    ("nullif", KeywordCategory::ColName),
+    ("numeric", KeywordCategory::ColName),
+    ("offset", KeywordCategory::Reserved),
+    ("on", KeywordCategory::Reserved),
+    ("only", KeywordCategory::Reserved),
+    ("or", KeywordCategory::Reserved),
+    ("order", KeywordCategory::Reserved),
+    ("out", KeywordCategory::ColName),
+    ("outer", KeywordCategory::TypeFuncName),
+    ("overlaps", KeywordCategory::TypeFuncName),
+    ("overlay", KeywordCategory::ColName),
+    ("placing", KeywordCategory::Reserved),
+    ("position", KeywordCategory::ColName),
+    ("precision", KeywordCategory::ColName),
+    ("primary", KeywordCategory::Reserved),
+    ("real", KeywordCategory::ColName),
+    ("references", KeywordCategory::Reserved),
+    ("returning", KeywordCategory::Reserved),
+    ("right", KeywordCategory::TypeFuncName),
+    ("row", KeywordCategory::ColName),
+    ("select", KeywordCategory::Reserved),
+    ("session_user", KeywordCategory::Reserved),
+    ("setof", KeywordCategory::ColName),
+    ("similar", KeywordCategory::TypeFuncName),
+    ("smallint", KeywordCategory::ColName),
+    ("some", KeywordCategory::Reserved),
+    ("substring", KeywordCategory::ColName),
+    ("symmetric", KeywordCategory::Reserved),
+    ("system_user", KeywordCategory::Reserved),
+    ("table", KeywordCategory::Reserved),
+    ("tablesample", KeywordCategory::TypeFuncName),
+    ("then", KeywordCategory::Reserved),
+    ("time", KeywordCategory::ColName),
+    ("timestamp", KeywordCategory::ColName),
+    ("to", KeywordCategory::Reserved),
+    ("trailing", KeywordCategory::Reserved),
+    ("treat", KeywordCategory::ColName),
+    ("trim", KeywordCategory::ColName),
+    ("true", KeywordCategory::Reserved),
+    ("union", KeywordCategory::Reserved),
+    ("unique", KeywordCategory::Reserved),
+    ("user", KeywordCategory::Reserved),
+    ("using", KeywordCategory::Reserved),
+    ("values", KeywordCategory::ColName),
+    ("varchar", KeywordCategory::ColName),
+    ("variadic", KeywordCategory::Reserved),
+    ("verbose", KeywordCategory::TypeFuncName),
+    ("when", KeywordCategory::Reserved),
+    ("where", KeywordCategory::Reserved),
+    ("window", KeywordCategory::Reserved),
+    ("with", KeywordCategory::Reserved),
+    ("xmlattributes", KeywordCategory::ColName),
+    ("xmlconcat", KeywordCategory::ColName),
+    ("xmlelement", KeywordCategory::ColName),
+    ("xmlexists", KeywordCategory::ColName),
+    ("xmlforest", KeywordCategory::ColName),
+    ("xmlnamespaces", KeywordCategory::ColName),
+    ("xmlparse", KeywordCategory::ColName),
+    ("xmlpi", KeywordCategory::ColName),
+    ("xmlroot", KeywordCategory::ColName),
+    ("xmlserialize", KeywordCategory::ColName),
+    ("xmltable", KeywordCategory::ColName),
+];
+
+pub fn keyword_category(word: &str) -> Option<KeywordCategory> {
+    KEYWORDS
+        .binary_search_by(|(keyword, _)| keyword.cmp(&word))
+        .ok()
+        .map(|i| KEYWORDS[i].1)
+}
+
+// Keywords that must be quoted to be used as table or column names
+pub fn is_reserved(word: &str) -> bool {
+    matches!(keyword_category(word), Some(KeywordCategory::Reserved | KeywordCategory::TypeFuncName))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorted() {
+        assert!(KEYWORDS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(keyword_category("select"), Some(KeywordCategory::Reserved));
+        assert_eq!(keyword_category("left"), Some(KeywordCategory::TypeFuncName));
+        assert_eq!(keyword_category("int"), Some(KeywordCategory::ColName));
+        assert_eq!(keyword_category("name"), None);
+        assert_eq!(keyword_category("SELECT"), None);
+    }
+
+    #[test]
+    fn reserved() {
+        for word in ["select", "where", "user", "table", "order", "join", "left"] {
+            assert!(is_reserved(word), "{}", word);
+        }
+        for word in ["name", "type", "int", "values", "users"] {
+            assert!(!is_reserved(word), "{}", word);
+        }
+    }
+
+}
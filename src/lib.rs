@@ -1,12 +1,38 @@
 
 pub mod error;
+pub mod factory;
 pub mod name;
 pub mod ident;
+pub mod lint;
+pub mod naming;
+pub mod query;
+pub mod quoting;
+pub mod render;
+pub mod scan;
+pub mod search_path;
+pub mod subobject;
+pub mod subscript;
+pub mod writer;
+mod keywords;
+mod parse;
+#[cfg(feature = "serde")]
+mod serialize;
 mod util;
 
 pub use error::IdentError;
-pub use name::PgName;
-pub use ident::PgIdent;
+pub use factory::NameFactory;
+pub use name::{common_schema, parse_name_list, CatalogKeys, NameDiff, ParsedName, PgName};
+pub use ident::{quote_ident_exact, Adjustment, CaseMode, Charset, PgIdent, QuoteReason};
+pub use lint::detect_confusable;
+pub use naming::default_index_name;
+pub use query::{copy_statement, grant_statement, names_to_pair_tuples, rename_statement, revoke_statement};
+pub use quoting::{PreferredIdent, QuotePreference};
+pub use render::{QuoteStyle, RenderOptions};
+pub use scan::scan_identifiers;
+pub use search_path::normalize_search_path;
+pub use subobject::SubObjectName;
+pub use subscript::{Subscript, SubscriptedName};
+pub use writer::IdentWriter;
 
 
 #[cfg(test)]
@@ -0,0 +1,56 @@
+use crate::{PgIdent, PgName};
+use crate::util::fold;
+
+fn is_quoted<T: AsRef<str>>(id: &PgIdent<T>) -> bool {
+    matches!(id, PgIdent::Quoted(_))
+}
+
+// Flags pairs of names (by index) that only differ in quoting and case, such as `Foo` and
+// `"foo"`, where one is quoted and the other bare. These are either the same object, or
+// different objects that are easy to mix up.
+pub fn detect_confusable<T: AsRef<str>>(names: &[PgName<T>]) -> Vec<(usize, usize)> {
+    let confusable = |a: &PgName<T>, b: &PgName<T>| {
+        let (a, b) = (a.parts(), b.parts());
+        a.len() == b.len()
+            && a.iter().zip(&b).all(|(x, y)| fold(&x.as_value()) == fold(&y.as_value()))
+            && a.iter().zip(&b).any(|(x, y)| is_quoted(x) != is_quoted(y))
+    };
+    let mut pairs = Vec::new();
+    for (i, a) in names.iter().enumerate() {
+        for (j, b) in names.iter().enumerate().skip(i + 1) {
+            if confusable(a, b) {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confusable_pairs() {
+        let names: Vec<PgName<&str>> = vec![
+            PgName::new("Foo").unwrap(),
+            PgName::new("foo").unwrap(),
+            PgName::new("bar").unwrap(),
+            PgName::Id(PgIdent::Quoted("foo".to_string())),
+        ];
+        assert_eq!(detect_confusable(&names), vec![(0, 1), (1, 3)]);
+    }
+
+    #[test]
+    fn confusable_qualified() {
+        let names: Vec<PgName<&str>> = vec![
+            PgName::new_ns(vec!["public", "Users"]).unwrap(),
+            PgName::new_ns(vec!["public", "users"]).unwrap(),
+            PgName::new_ns(vec!["audit", "users"]).unwrap(),
+            PgName::new("users").unwrap(),
+        ];
+        assert_eq!(detect_confusable(&names), vec![(0, 1)]);
+    }
+
+}
@@ -1,6 +1,14 @@
 use crate::{PgIdent, IdentError};
-use std::fmt::{Display, Formatter};
+use crate::parse::Parser;
+use crate::util::{escape_json, escape_literal, fold, is_ident_compatible};
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Write};
+use std::hash::{Hash, Hasher};
+use std::path::{Component, Path};
+use std::str::FromStr;
 
+// `Namespaced` always holds at least one part. The constructors never build an empty one,
+// and accessors such as `name` and `first` panic if one is built by hand.
 #[derive(Debug)]
 pub enum PgName<T> where T: AsRef<str> {
     Id(PgIdent<T>),
@@ -8,6 +16,44 @@ pub enum PgName<T> where T: AsRef<str> {
     Namespaced(Vec<PgIdent<T>>),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NameDiff {
+    // Any qualifying part (schema/catalog) refers to a different object, or was added/removed
+    pub schema_changed: bool,
+    pub relation_changed: bool,
+    // The names refer to the same object, but are rendered differently
+    pub quoting_only: bool,
+}
+
+// The values stored in `pg_namespace.nspname` and `pg_class.relname` for a name,
+// ready to bind as query parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogKeys {
+    pub schema: Option<String>,
+    pub relation: String,
+}
+
+// A name read by `PgName::parse_with_source_quoting`, along with which parts were written in
+// double quotes. That can differ from how the name renders, e.g. a bare `Équipe` is quoted.
+#[derive(Debug)]
+pub struct ParsedName {
+    pub name: PgName<String>,
+    source_quoting: Vec<bool>,
+}
+
+impl ParsedName {
+    // One entry per part of `name`
+    pub fn source_quoting(&self) -> &[bool] {
+        &self.source_quoting
+    }
+}
+
+impl NameDiff {
+    pub fn is_same_object(&self) -> bool {
+        !self.schema_changed && !self.relation_changed
+    }
+}
+
 impl <T: AsRef<str>> PgName<T> {
 
     pub fn new(id: T) -> Result<Self, IdentError> {
@@ -21,13 +67,59 @@ impl <T: AsRef<str>> PgName<T> {
         for id in ns {
             ids.push(PgIdent::new(id)?);
         }
+        ids.try_into()
+    }
+
+    pub fn from_optional_schema(schema: Option<T>, relation: T) -> Result<Self, IdentError> {
+        let relation = PgIdent::new(relation)?;
+        Ok(match schema {
+            Some(schema) => Self::IdPair(PgIdent::new(schema)?, relation),
+            None => Self::Id(relation),
+        })
+    }
+
+    // For a schema fixed at compile time (e.g. an extension's own schema), only the relation
+    // is validated. The schema is validated once by the caller, e.g. into a `static`, and is
+    // then copied into each name as-is.
+    pub fn in_static_schema(schema: &'static PgIdent<&'static str>, relation: T) -> Result<Self, IdentError>
+    where T: From<&'static str>
+    {
+        let schema = match schema {
+            PgIdent::Id(id) => PgIdent::Id(T::from(*id)),
+            PgIdent::Quoted(escaped) => PgIdent::Quoted(escaped.clone()),
+        };
+        Ok(Self::IdPair(schema, PgIdent::new(relation)?))
+    }
+
+    // Like `new_ns`, but the error says which part was invalid
+    pub fn new_ns_indexed<U>(ns: U) -> Result<Self, (usize, IdentError)>
+    where U: IntoIterator<Item=T>
+    {
+        let mut ids = Vec::new();
+        for (index, id) in ns.into_iter().enumerate() {
+            ids.push(PgIdent::new(id).map_err(|e| (index, e))?);
+        }
+        ids.try_into().map_err(|e| (0, e))
+    }
+
+    fn from_idents(mut ids: Vec<PgIdent<T>>) -> Self {
         if ids.len() == 1 {
-            return Ok(Self::Id(ids.pop().unwrap()));
+            return Self::Id(ids.pop().unwrap());
         }
         if ids.len() == 2 {
-            return Ok(Self::IdPair(ids.remove(0), ids.remove(0)));
+            // Consume the vec rather than remove(0), which would shift the remaining element
+            let mut it = ids.into_iter();
+            return Self::IdPair(it.next().unwrap(), it.next().unwrap());
+        }
+        Self::Namespaced(ids)
+    }
+
+    // Returns the name back unchanged if it's qualified
+    pub fn into_ident(self) -> Result<PgIdent<T>, PgName<T>> {
+        match self {
+            Self::Id(id) => Ok(id),
+            _ => Err(self),
         }
-        Ok(Self::Namespaced(ids))
     }
 
     pub fn name(&self) -> &PgIdent<T> {
@@ -37,6 +129,311 @@ impl <T: AsRef<str>> PgName<T> {
             Self::Namespaced(ids) => ids.last().unwrap(),
         }
     }
+
+    pub fn first(&self) -> &PgIdent<T> {
+        match self {
+            Self::Id(id) => id,
+            Self::IdPair(schema, _) => schema,
+            Self::Namespaced(ids) => ids.first().unwrap(),
+        }
+    }
+
+    pub fn last(&self) -> &PgIdent<T> {
+        self.name()
+    }
+
+    // Position-aware accessors: two-part names are `schema.relation`, and three-part names
+    // are `catalog.schema.relation`. Longer names (e.g. column references) have neither.
+    pub fn catalog(&self) -> Option<&PgIdent<T>> {
+        match self.to_array() {
+            ([Some(catalog), _, _], 3) => Some(catalog),
+            _ => None,
+        }
+    }
+
+    pub fn schema(&self) -> Option<&PgIdent<T>> {
+        match self.to_array() {
+            ([Some(schema), _, _], 2) | ([_, Some(schema), _], 3) => Some(schema),
+            _ => None,
+        }
+    }
+
+    pub fn relation(&self) -> &PgIdent<T> {
+        self.name()
+    }
+
+    // The parts in positional slots, without allocating. Names with more than three parts
+    // leave every slot empty, and the count shows how many parts there are.
+    pub fn to_array(&self) -> ([Option<&PgIdent<T>>; 3], usize) {
+        match self {
+            Self::Id(id) => ([Some(id), None, None], 1),
+            Self::IdPair(schema, table) => ([Some(schema), Some(table), None], 2),
+            Self::Namespaced(ids) => match ids.as_slice() {
+                [a] => ([Some(a), None, None], 1),
+                [a, b] => ([Some(a), Some(b), None], 2),
+                [a, b, c] => ([Some(a), Some(b), Some(c)], 3),
+                _ => ([None, None, None], ids.len()),
+            },
+        }
+    }
+
+    pub fn is_bare_single(&self) -> bool {
+        matches!(self, Self::Id(PgIdent::Id(_)))
+    }
+
+    // For statement builders to warn about: a `;` is valid in a quoted name, but unusual
+    pub fn contains_statement_terminator(&self) -> bool {
+        self.parts().iter().any(|id| id.as_value().contains(';'))
+    }
+
+    // True when every part is bare, i.e. a lower-case, non-reserved name that needs no quoting
+    pub fn is_conventional(&self) -> bool {
+        self.parts().iter().all(|part| part.classify().is_none())
+    }
+
+    // For planning a migration to lower-case names: true for each quoted part that would no
+    // longer need quoting once lower-cased, e.g. `"Users"`, but not `"My Table"`.
+    // Parts that are already bare are false.
+    pub fn would_be_bare_after_fold(&self) -> Vec<bool> {
+        self.parts()
+            .iter()
+            .map(|part| matches!(part, PgIdent::Quoted(_)) && is_ident_compatible(&part.as_value().to_lowercase()))
+            .collect()
+    }
+
+    // For `\dt app_*`-style filters. The prefix is folded like an unquoted name, so `App`
+    // matches `app_users` but not `"App_users"`.
+    pub fn relation_starts_with(&self, prefix: &str) -> bool {
+        self.relation().folded().starts_with(fold(prefix).as_ref())
+    }
+
+    // As `relation_starts_with`, but for the schema. Unqualified names never match.
+    pub fn schema_starts_with(&self, prefix: &str) -> bool {
+        self.schema().is_some_and(|schema| schema.folded().starts_with(fold(prefix).as_ref()))
+    }
+
+    // A heuristic lint, without any catalog knowledge: an unqualified name could resolve to
+    // more than one object when there's more than one schema on the search path.
+    pub fn is_potentially_ambiguous(&self, search_path_len: usize) -> bool {
+        matches!(self, Self::Id(_)) && search_path_len > 1
+    }
+
+    // Compares with unqualified names treated as being in `default`, so `users` equals
+    // `public.users` when the default is `public`.
+    pub fn eq_with_default_schema(&self, other: &PgName<T>, default: &PgIdent<T>) -> bool {
+        let (ours, theirs) = (self.parts_with_default(default), other.parts_with_default(default));
+        ours.len() == theirs.len() && ours.iter().zip(theirs).all(|(a, b)| a.eq_ignore_quoting(b))
+    }
+
+    fn parts_with_default<'a>(&'a self, default: &'a PgIdent<T>) -> Vec<&'a PgIdent<T>> {
+        match self {
+            Self::Id(id) => vec![default, id],
+            _ => self.parts(),
+        }
+    }
+
+    pub fn diff(&self, other: &PgName<T>) -> NameDiff {
+        let (ours, theirs) = (self.parts(), other.parts());
+        let (our_schema, their_schema) = (&ours[..ours.len().saturating_sub(1)], &theirs[..theirs.len().saturating_sub(1)]);
+        let schema_changed = our_schema.len() != their_schema.len()
+            || our_schema.iter().zip(their_schema).any(|(a, b)| a.folded() != b.folded());
+        let relation_changed = self.name().folded() != other.name().folded();
+        NameDiff {
+            schema_changed,
+            relation_changed,
+            quoting_only: !schema_changed && !relation_changed && self.to_string() != other.to_string(),
+        }
+    }
+
+    // Compares against the output of `'name'::regclass::text`. Postgres leaves out the schema
+    // when it's visible in the search path, so fewer server-side parts only need to match
+    // our trailing parts.
+    pub fn equals_regclass_text(&self, s: &str) -> bool {
+        let mut parser = Parser::new(s.trim());
+        let Ok(theirs) = parser.name() else {
+            return false;
+        };
+        let ours = self.parts();
+        parser.is_at_end()
+            && theirs.len() <= ours.len()
+            && ours[ours.len() - theirs.len()..].iter().zip(&theirs).all(|(a, b)| a.eq_ignore_quoting(b))
+    }
+
+    pub fn catalog_keys(&self) -> CatalogKeys {
+        CatalogKeys {
+            schema: self.schema().map(|schema| schema.folded().into_owned()),
+            relation: self.relation().folded().into_owned(),
+        }
+    }
+
+    // The unescaped value of each part, suitable for passing back to `new_ns`
+    pub fn as_values(&self) -> Vec<Cow<'_, str>> {
+        self.parts().into_iter().map(|id| id.as_value()).collect()
+    }
+
+    pub(crate) fn parts(&self) -> Vec<&PgIdent<T>> {
+        match self {
+            Self::Id(id) => vec![id],
+            Self::IdPair(schema, table) => vec![schema, table],
+            Self::Namespaced(ids) => ids.iter().collect(),
+        }
+    }
+
+    pub fn rendered_len(&self) -> usize {
+        let parts = self.parts();
+        parts.iter().map(|id| id.rendered_len()).sum::<usize>() + parts.len().saturating_sub(1)
+    }
+
+    // Borrows for the common single bare identifier case, rather than allocating
+    pub fn render(&self) -> Cow<'_, str> {
+        match self {
+            Self::Id(PgIdent::Id(id)) => Cow::Borrowed(id.as_ref()),
+            _ => Cow::Owned(self.to_sql()),
+        }
+    }
+
+    pub fn to_sql(&self) -> String {
+        let mut sql = String::new();
+        self.render_into(&mut sql);
+        sql
+    }
+
+    // Appends to `buf`, without clearing it, so one buffer can be reused for many names
+    pub fn render_into(&self, buf: &mut String) {
+        buf.reserve(self.rendered_len());
+        write!(buf, "{}", self).unwrap();
+    }
+
+    // An explicit checkpoint before pasting the name into SQL text: the rendered form is only
+    // returned after checking each part directly, so that bare parts are valid bare names and
+    // quoted parts have every `"` doubled and no NUL, meaning nothing can escape identifier context.
+    pub fn assert_safe_for_interpolation(&self) -> Result<Cow<'_, str>, IdentError> {
+        let escaped = |inner: &str| {
+            !inner.is_empty() && !inner.contains('\x00') && inner.split("\"\"").all(|chunk| !chunk.contains('"'))
+        };
+        let safe = self.parts().iter().all(|id| match id {
+            PgIdent::Id(id) => is_ident_compatible(id.as_ref()),
+            PgIdent::Quoted(inner) => escaped(inner),
+        });
+        let rendered = self.render();
+        if safe {
+            Ok(rendered)
+        } else {
+            Err(IdentError::UnsafeRenderingError(rendered.into_owned()))
+        }
+    }
+
+    // Renders the name over multiple lines when it's wider than `max_width` characters,
+    // breaking only after a dot. `continuation` goes between lines, e.g. `"\n"` or `"\\\n    "`,
+    // and any text after its last newline counts towards the next line's width.
+    // A single part wider than `max_width` is never split.
+    pub fn render_wrapped(&self, max_width: usize, continuation: &str) -> String {
+        let indent = continuation.rsplit('\n').next().unwrap_or_default().chars().count();
+        let parts = self.parts();
+        let mut rendered = String::new();
+        let mut width = 0;
+        for (i, id) in parts.iter().enumerate() {
+            let mut piece = id.to_string();
+            if i + 1 < parts.len() {
+                piece.push('.');
+            }
+            let piece_width = piece.chars().count();
+            if i > 0 && width + piece_width > max_width {
+                rendered.push_str(continuation);
+                width = indent;
+            }
+            rendered.push_str(&piece);
+            width += piece_width;
+        }
+        rendered
+    }
+
+    // Orders less-qualified names first, so schemas sort before the relations they contain
+    // (`public` < `public.users`), then by the parts' identities.
+    pub fn creation_sort_key(&self) -> impl Ord {
+        let parts = self.parts();
+        (parts.len(), parts.iter().map(|id| id.folded().into_owned()).collect::<Vec<_>>())
+    }
+
+    // For inspecting why a name is quoted, e.g. `public."Users"  -- quoted: case`.
+    // With several quoted parts, each reason is followed by its part. Not meant to be executed.
+    pub fn render_annotated(&self) -> String {
+        let quoted: Vec<_> = self
+            .parts()
+            .into_iter()
+            .filter_map(|id| id.classify().map(|reason| (id, reason)))
+            .collect();
+        let sql = self.to_sql();
+        match quoted.as_slice() {
+            [] => sql,
+            [(_, reason)] => format!("{}  -- quoted: {}", sql, reason),
+            _ => {
+                let reasons: Vec<_> = quoted.iter().map(|(id, reason)| format!("{} ({})", reason, id)).collect();
+                format!("{}  -- quoted: {}", sql, reasons.join(", "))
+            }
+        }
+    }
+
+    // The unquoted values joined with dots, e.g. `public.My Table`, for logs and EXPLAIN-style
+    // output. This is NOT valid SQL, and can't be parsed back reliably.
+    pub fn to_logical_display(&self) -> String {
+        self.as_values().join(".")
+    }
+
+    // A line per part, giving the rendered form, the value as given and the key used for
+    // comparisons, e.g. `"Users" value="Users" key="Users"`, to show why names differ.
+    pub fn fold_report(&self) -> String {
+        self.parts()
+            .iter()
+            .map(|id| format!("{} value={:?} key={:?}", id, id.as_value(), id.folded()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // A log-friendly label such as `cat:db/ns:public/rel:users`. This is not SQL.
+    pub fn debug_label(&self) -> String {
+        let parts = self.parts();
+        parts
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let tag = if i + 1 == parts.len() {
+                    "rel"
+                } else if i == 0 && parts.len() == 3 {
+                    "cat"
+                } else {
+                    "ns"
+                };
+                format!("{}:{}", tag, id.as_value())
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    pub fn with_only_modifier(&self) -> String {
+        format!("ONLY {}", self)
+    }
+
+    // The rendered name, escaped for use inside a JSON string (without the surrounding quotes)
+    pub fn to_json_escaped(&self) -> String {
+        escape_json(&self.to_string())
+    }
+
+    // Renders foldable parts as bare lower-case, and everything else quoted in upper-case,
+    // which is the canonical form some downstream tools expect.
+    pub fn render_tool_canonical(&self) -> String {
+        self.parts()
+            .iter()
+            .map(|id| id.tool_canonical())
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    // The rendered name, escaped so it can be placed inside a single-quoted
+    // string literal, e.g. for `EXECUTE '... ' || ...` style dynamic SQL.
+    pub fn to_dynamic_sql_embedded(&self) -> String {
+        escape_literal(&self.to_string())
+    }
 }
 
 impl <T: AsRef<str> + Clone> PgName<T> {
@@ -44,7 +441,7 @@ impl <T: AsRef<str> + Clone> PgName<T> {
         let name_part = PgIdent::new(name_part)?;
         Ok(match self {
             Self::Id(_) => Self::Id(name_part),
-            Self::IdPair(schema, _) => Self::IdPair(schema.clone().into(), name_part),
+            Self::IdPair(schema, _) => Self::IdPair(schema.clone(), name_part),
             Self::Namespaced(ids) => {
                 let mut new_parts = ids[..ids.len()-1].to_vec();
                 new_parts.push(name_part);
@@ -52,6 +449,114 @@ impl <T: AsRef<str> + Clone> PgName<T> {
             }
         })
     }
+
+    // Prepends a part, so `users` becomes `schema.users` and `app.users` becomes `db.app.users`
+    pub fn with_schema(&self, schema: T) -> Result<PgName<T>, IdentError> {
+        let schema = PgIdent::new(schema)?;
+        Ok(match self {
+            Self::Id(name) => Self::IdPair(schema, name.clone()),
+            Self::IdPair(first, second) => Self::Namespaced(vec![schema, first.clone(), second.clone()]),
+            Self::Namespaced(ids) => {
+                let mut new_parts = vec![schema];
+                new_parts.extend(ids.iter().cloned());
+                Self::Namespaced(new_parts)
+            }
+        })
+    }
+
+    // Appends a part, e.g. to reference a column in this table as `app.users.email`
+    pub fn qualify(&self, part: T) -> Result<PgName<T>, IdentError> {
+        let part = PgIdent::new(part)?;
+        Ok(match self {
+            Self::Id(name) => Self::IdPair(name.clone(), part),
+            Self::IdPair(first, second) => Self::Namespaced(vec![first.clone(), second.clone(), part]),
+            Self::Namespaced(ids) => {
+                let mut new_parts = ids.to_vec();
+                new_parts.push(part);
+                Self::Namespaced(new_parts)
+            }
+        })
+    }
+
+    // For tools that only understand `schema.relation`: unqualified names get `default_schema`,
+    // and the catalog of a three-part name is dropped. Following the position-aware rules of
+    // `schema`, longer names (e.g. column references) have no schema and are an error.
+    pub fn to_two_level(&self, default_schema: &PgIdent<T>) -> Result<(PgIdent<T>, PgIdent<T>), IdentError> {
+        match self.to_array() {
+            ([Some(relation), None, None], 1) => Ok((default_schema.clone(), relation.clone())),
+            ([Some(schema), Some(relation), None], 2) | ([_, Some(schema), Some(relation)], 3) => {
+                Ok((schema.clone(), relation.clone()))
+            }
+            (_, actual) => Err(IdentError::PartCountError { expected: 3, actual }),
+        }
+    }
+
+    // Drops the qualification when no other name in `known` shares the same relation name.
+    pub fn minimal_reference(&self, known: &[PgName<T>]) -> PgName<T> {
+        let name = self.name().folded();
+        let ambiguous = known
+            .iter()
+            .any(|other| self != other && other.name().folded() == name);
+        if ambiguous {
+            self.clone()
+        } else {
+            Self::Id(self.name().clone())
+        }
+    }
+
+    // Drops the schema when it's the given default, so `public.users` becomes `users`
+    pub fn simplify(&self, default_schema: &PgIdent<T>) -> PgName<T> {
+        match self {
+            Self::IdPair(schema, table) if schema.eq_ignore_quoting(default_schema) => Self::Id(table.clone()),
+            _ => self.clone(),
+        }
+    }
+
+    // Adds the default schema to unqualified names, so the result doesn't depend on search_path
+    pub fn force_qualified(&self, default_schema: &PgIdent<T>) -> PgName<T> {
+        match self {
+            Self::Id(id) => Self::IdPair(default_schema.clone(), id.clone()),
+            _ => self.clone(),
+        }
+    }
+
+    pub fn retain_parts<F: Fn(usize, &PgIdent<T>) -> bool>(&self, f: F) -> Result<PgName<T>, IdentError> {
+        let ids: Vec<PgIdent<T>> = self
+            .parts()
+            .into_iter()
+            .enumerate()
+            .filter(|(i, id)| f(*i, id))
+            .map(|(_, id)| id.clone())
+            .collect();
+        ids.try_into()
+    }
+
+    // Moves the name to another schema. The relation (and any catalog) keeps its existing
+    // quoting, since it's carried over as-is rather than rebuilt from its value. As with
+    // `schema`, names of more than three parts have no schema to replace.
+    pub fn replace_schema(&self, schema: T) -> Result<PgName<T>, IdentError> {
+        let schema = PgIdent::new(schema)?;
+        match self.to_array() {
+            ([Some(relation), None, None], 1) | ([_, Some(relation), None], 2) => {
+                Ok(Self::IdPair(schema, relation.clone()))
+            }
+            ([Some(catalog), _, Some(relation)], 3) => {
+                Ok(Self::Namespaced(vec![catalog.clone(), schema, relation.clone()]))
+            }
+            (_, actual) => Err(IdentError::PartCountError { expected: 3, actual }),
+        }
+    }
+
+    pub fn with_catalog(&self, catalog: T) -> Result<PgName<T>, IdentError> {
+        match self {
+            Self::IdPair(schema, table) => Ok(Self::Namespaced(vec![
+                PgIdent::new(catalog)?,
+                schema.clone(),
+                table.clone(),
+            ])),
+            _ => Err(IdentError::PartCountError { expected: 2, actual: self.parts().len() }),
+        }
+    }
 }
 
 impl<T: AsRef<str>> Display for PgName<T> {
@@ -75,6 +580,145 @@ impl<T: AsRef<str>> Display for PgName<T> {
     }
 }
 
+impl PgName<String> {
+    // Parses a rendered name such as `public."My Table"`, the inverse of `Display`.
+    // Bare parts are folded to lower-case, and dots inside quoted parts are kept.
+    pub fn parse(s: &str) -> Result<PgName<String>, IdentError> {
+        Self::parse_with(s, false)
+    }
+
+    // Like `parse`, but tolerates doubled dots between parts, e.g. `public..users`
+    pub fn parse_lenient(s: &str) -> Result<PgName<String>, IdentError> {
+        Self::parse_with(s, true)
+    }
+
+    // Like `parse`, but also records which parts were written in double quotes
+    pub fn parse_with_source_quoting(s: &str) -> Result<ParsedName, IdentError> {
+        let (ids, source_quoting) = Self::parse_parts(s, false)?;
+        Ok(ParsedName { name: ids.try_into()?, source_quoting })
+    }
+
+    fn parse_with(s: &str, collapse_dots: bool) -> Result<PgName<String>, IdentError> {
+        Self::parse_parts(s, collapse_dots)?.0.try_into()
+    }
+
+    fn parse_parts(s: &str, collapse_dots: bool) -> Result<(Vec<PgIdent<String>>, Vec<bool>), IdentError> {
+        let mut parser = Parser::new(s);
+        let parts = parser.name_with_quoting(collapse_dots)?;
+        if !parser.is_at_end() {
+            return Err(parser.unexpected());
+        }
+        Ok(parts)
+    }
+
+    // Treats each component of a relative path as a part, e.g. `public/users`
+    pub fn from_path(p: &Path) -> Result<PgName<String>, IdentError> {
+        let mut parts = Vec::new();
+        for component in p.components() {
+            let part = match component {
+                Component::Normal(part) => part.to_str(),
+                _ => None,
+            };
+            let Some(part) = part else {
+                return Err(IdentError::PathComponentError(component.as_os_str().to_string_lossy().into_owned()));
+            };
+            parts.push(PgIdent::new(part.to_string())?);
+        }
+        parts.try_into()
+    }
+
+    pub fn try_from_parts_indexed<I>(i: I) -> Result<PgName<String>, (usize, IdentError)>
+    where I: IntoIterator, I::Item: AsRef<str>
+    {
+        PgName::new_ns_indexed(i.into_iter().map(|part| part.as_ref().to_string()))
+    }
+
+    // Splits names from non-SQL sources such as `schema:table` or `schema/table`.
+    // There's no quoting in these formats, so each part is taken as-is.
+    pub fn from_delimited(s: &str, delim: char) -> Result<PgName<String>, IdentError> {
+        PgName::new_ns(s.split(delim).map(|part| part.to_string()))
+    }
+}
+
+impl<T> Clone for PgName<T> where T: AsRef<str> + Clone {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Id(id) => Self::Id(id.clone()),
+            Self::IdPair(schema, table) => Self::IdPair(schema.clone(), table.clone()),
+            Self::Namespaced(ids) => Self::Namespaced(ids.clone()),
+        }
+    }
+}
+
+// Names are equal when they have the same number of parts, and each part is equal
+// under `PgIdent`'s Postgres identity rules.
+impl<T: AsRef<str>> PartialEq for PgName<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.parts() == other.parts()
+    }
+}
+
+impl<T: AsRef<str>> Eq for PgName<T> {}
+
+impl<T: AsRef<str>> Hash for PgName<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.parts().hash(state);
+    }
+}
+
+// Compares against a (schema, relation) pair, where each side is interpreted the same way as
+// `TryFrom<(&str, &str)>` would.
+impl<T: AsRef<str>> PartialEq<(&str, &str)> for PgName<T> {
+    fn eq(&self, other: &(&str, &str)) -> bool {
+        match self {
+            Self::IdPair(schema, table) => schema.folded() == other.0 && table.folded() == other.1,
+            _ => false,
+        }
+    }
+}
+
+// Compares part-wise against the values Postgres would use for each part, so
+// `public."Users"` equals `["public", "Users"]`, but `public.Users` doesn't.
+impl<T: AsRef<str>> PartialEq<&[&str]> for PgName<T> {
+    fn eq(&self, other: &&[&str]) -> bool {
+        let parts = self.parts();
+        parts.len() == other.len() && parts.iter().zip(other.iter()).all(|(id, part)| id.folded() == *part)
+    }
+}
+
+impl<T: AsRef<str>> PartialEq<Vec<&str>> for PgName<T> {
+    fn eq(&self, other: &Vec<&str>) -> bool {
+        *self == other.as_slice()
+    }
+}
+
+impl<T: AsRef<str>> TryFrom<Vec<PgIdent<T>>> for PgName<T> {
+    type Error = IdentError;
+
+    fn try_from(value: Vec<PgIdent<T>>) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            return Err(IdentError::ZeroLengthError());
+        }
+        Ok(PgName::from_idents(value))
+    }
+}
+
+impl<T: AsRef<str> + Clone> TryFrom<&[PgIdent<T>]> for PgName<T> {
+    type Error = IdentError;
+
+    fn try_from(value: &[PgIdent<T>]) -> Result<Self, Self::Error> {
+        value.to_vec().try_into()
+    }
+}
+
+impl FromStr for PgName<String> {
+    type Err = IdentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PgName::parse(s)
+    }
+}
+
 impl TryFrom<&str> for PgName<String> {
     type Error = IdentError;
 
@@ -119,11 +763,68 @@ impl TryFrom<Vec<String>> for PgName<String> {
     }
 }
 
+// Parses a comma-separated list such as `public.users, "a,b".events`. Commas inside quoted
+// parts don't split entries, and whitespace around entries is ignored.
+pub fn parse_name_list(s: &str) -> Result<Vec<PgName<String>>, IdentError> {
+    let mut parser = Parser::new(s);
+    let mut names = Vec::new();
+    loop {
+        parser.skip_whitespace();
+        names.push(parser.name()?.try_into()?);
+        parser.skip_whitespace();
+        if parser.is_at_end() {
+            return Ok(names);
+        }
+        if !parser.eat(',') {
+            return Err(parser.unexpected());
+        }
+    }
+}
+
+// The schema shared by all of `names`, or None if any differ, any name is unqualified,
+// or there are no names. Schemas are compared as Postgres would, so `App` matches `app`.
+pub fn common_schema<T: AsRef<str> + Clone>(names: &[PgName<T>]) -> Option<PgIdent<T>> {
+    let (first, rest) = names.split_first()?;
+    let schema = first.schema()?;
+    for name in rest {
+        if !name.schema()?.eq_ignore_quoting(schema) {
+            return None;
+        }
+    }
+    Some(schema.clone())
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn common_schemas() {
+        let names = parse_name_list("app.users, APP.orders, \"app\".events").unwrap();
+        assert_eq!(common_schema(&names).unwrap().to_string(), "app");
+        let names = parse_name_list("app.users, public.orders").unwrap();
+        assert!(common_schema(&names).is_none());
+        let names = parse_name_list("app.users, orders").unwrap();
+        assert!(common_schema(&names).is_none());
+        assert!(common_schema::<String>(&[]).is_none());
+    }
+
+    #[test]
+    fn name_list() {
+        let names = parse_name_list("public.users, analytics.events").unwrap();
+        let rendered: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        assert_eq!(rendered, vec!["public.users", "analytics.events"]);
+
+        let names = parse_name_list("  \"a,b\".\"c, d\" ,users\t").unwrap();
+        let rendered: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        assert_eq!(rendered, vec!["\"a,b\".\"c, d\"", "users"]);
+
+        assert!(parse_name_list("").is_err());
+        assert!(parse_name_list("users,").is_err());
+        assert!(matches!(parse_name_list("users; drop"), Err(IdentError::UnexpectedCharError { ch: ';', offset: 5 })));
+    }
+
     #[test]
     fn from_str() {
         let id: PgName<String> = "foo".try_into().unwrap();
@@ -144,6 +845,207 @@ mod tests {
         assert_eq!(format!("{}", id), "foo.bar");
     }
 
+    #[test]
+    fn eq_tuple() {
+        let id: PgName<String> = ("public", "users").try_into().unwrap();
+        assert_eq!(id, ("public", "users"));
+        assert_ne!(id, ("public", "Users"));
+        assert_ne!(id, ("other", "users"));
+
+        let id: PgName<String> = ("public", "Users").try_into().unwrap();
+        assert_eq!(id, ("public", "Users"));
+        assert_ne!(id, ("public", "users"));
+    }
+
+    #[test]
+    fn eq_tuple_arity() {
+        let id = PgName::new("users").unwrap();
+        assert_ne!(id, ("public", "users"));
+        let id = PgName::new_ns(vec!["db", "public", "users"]).unwrap();
+        assert_ne!(id, ("public", "users"));
+    }
+
+    #[test]
+    fn from_ident_slice() {
+        let ids: Vec<PgIdent<&str>> = vec![
+            PgIdent::new("db").unwrap(),
+            PgIdent::new("public").unwrap(),
+            PgIdent::new("Users").unwrap(),
+        ];
+        let id: PgName<&str> = ids[2..].try_into().unwrap();
+        assert!(matches!(id, PgName::Id(_)));
+        assert_eq!(format!("{}", id), "\"Users\"");
+        let id: PgName<&str> = ids[1..].try_into().unwrap();
+        assert!(matches!(id, PgName::IdPair(_, _)));
+        assert_eq!(format!("{}", id), "public.\"Users\"");
+        let id: PgName<&str> = ids[..].try_into().unwrap();
+        assert!(matches!(id, PgName::Namespaced(_)));
+        assert_eq!(format!("{}", id), "db.public.\"Users\"");
+        assert_eq!(ids.len(), 3);
+    }
+
+    #[test]
+    fn from_ident_vec() {
+        let id: PgName<&str> = vec![PgIdent::new("public").unwrap(), PgIdent::new("users").unwrap()].try_into().unwrap();
+        assert_eq!(format!("{}", id), "public.users");
+        let empty: &[PgIdent<&str>] = &[];
+        assert!(matches!(PgName::try_from(empty), Err(IdentError::ZeroLengthError())));
+    }
+
+    #[test]
+    fn ns_indexed() {
+        let id = PgName::new_ns_indexed(vec!["public", "users"]).unwrap();
+        assert!(matches!(id, PgName::IdPair(_, _)));
+        let err = PgName::new_ns_indexed(vec!["a", "b\x00", "c"]);
+        assert!(matches!(err, Err((1, IdentError::NullByteError()))));
+    }
+
+    #[test]
+    fn empty_parts() {
+        assert!(matches!(PgName::new(""), Err(IdentError::ZeroLengthError())));
+        assert!(matches!(PgName::new_ns(Vec::<&str>::new()), Err(IdentError::ZeroLengthError())));
+        assert!(matches!(PgName::new_ns(vec!["a", "", "c"]), Err(IdentError::ZeroLengthError())));
+        assert!(matches!(PgName::new_ns(vec!["", ""]), Err(IdentError::ZeroLengthError())));
+        assert!(matches!(PgName::new_ns_indexed(vec!["a", "", "c"]), Err((1, IdentError::ZeroLengthError()))));
+        assert!(matches!(PgName::new_ns_indexed(Vec::<&str>::new()), Err((0, IdentError::ZeroLengthError()))));
+    }
+
+    #[test]
+    fn parts_indexed() {
+        let id = PgName::try_from_parts_indexed(["db", "public", "users"]).unwrap();
+        assert_eq!(format!("{}", id), "db.public.users");
+
+        let err = PgName::try_from_parts_indexed(vec!["db", "public", "us\x00ers"]);
+        assert!(matches!(err, Err((2, IdentError::NullByteError()))));
+        let err = PgName::try_from_parts_indexed(Vec::<String>::new());
+        assert!(matches!(err, Err((0, IdentError::ZeroLengthError()))));
+    }
+
+    #[test]
+    fn parse() {
+        let id = PgName::parse("public.users").unwrap();
+        assert_eq!(id, ("public", "users"));
+        let id: PgName<String> = "Public.Users".parse().unwrap();
+        assert_eq!(id, ("public", "users"));
+
+        let id = PgName::parse("\"My Schema\".\"Weird.Table\"").unwrap();
+        assert_eq!(id, ("My Schema", "Weird.Table"));
+        let id = PgName::parse("\"a\"\"b\".c.d").unwrap();
+        assert!(matches!(id, PgName::Namespaced(_)));
+        assert_eq!(format!("{}", id.first()), "\"a\"\"b\"");
+        let id = PgName::parse("users").unwrap();
+        assert!(matches!(id, PgName::Id(_)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(PgName::parse(""), Err(IdentError::ZeroLengthError())));
+        assert!(matches!(PgName::parse(".users"), Err(IdentError::EmptySegment { index: 0 })));
+        assert!(matches!(PgName::parse("users."), Err(IdentError::EmptySegment { index: 1 })));
+        assert!(matches!(PgName::parse("public..users"), Err(IdentError::EmptySegment { index: 1 })));
+        assert!(matches!(PgName::parse("db.public...users"), Err(IdentError::EmptySegment { index: 2 })));
+        assert!(matches!(PgName::parse("public.\"users"), Err(IdentError::UnterminatedQuoteError())));
+        assert!(matches!(PgName::parse("public.\"\""), Err(IdentError::ZeroLengthError())));
+        assert!(matches!(
+            PgName::parse("public.users x"),
+            Err(IdentError::UnexpectedCharError { ch: ' ', offset: 12 })
+        ));
+        assert!(matches!(
+            PgName::parse("public.\"a\"b"),
+            Err(IdentError::UnexpectedCharError { ch: 'b', offset: 10 })
+        ));
+    }
+
+    #[test]
+    fn parse_lenient() {
+        assert_eq!(PgName::parse_lenient("public..users").unwrap().to_string(), "public.users");
+        assert_eq!(PgName::parse_lenient("db...public.\"a..b\"").unwrap().to_string(), "db.public.\"a..b\"");
+        assert!(matches!(PgName::parse_lenient("users.."), Err(IdentError::EmptySegment { index: 1 })));
+        assert!(matches!(PgName::parse_lenient("..users"), Err(IdentError::EmptySegment { index: 0 })));
+    }
+
+    #[test]
+    fn parse_display_round_trip() {
+        let names: Vec<PgName<&str>> = vec![
+            PgName::new("foo").unwrap(),
+            PgName::new_ns(vec!["My Schema", "Weird.Table"]).unwrap(),
+            PgName::new_ns(vec!["a", "B\"c", "d.e", "f"]).unwrap(),
+        ];
+        for name in names {
+            let rendered = name.to_string();
+            assert_eq!(PgName::parse(&rendered).unwrap().to_string(), rendered);
+        }
+    }
+
+    #[test]
+    fn from_path() {
+        let id = PgName::from_path(Path::new("public/users")).unwrap();
+        assert_eq!(id, ("public", "users"));
+        let id = PgName::from_path(Path::new("public/My Table")).unwrap();
+        assert_eq!(format!("{}", id), "public.\"My Table\"");
+
+        assert!(matches!(PgName::from_path(Path::new("/public/users")), Err(IdentError::PathComponentError(_))));
+        assert!(matches!(PgName::from_path(Path::new("public/../users")), Err(IdentError::PathComponentError(_))));
+        assert!(matches!(PgName::from_path(Path::new("")), Err(IdentError::ZeroLengthError())));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn from_path_non_unicode() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        let path = Path::new(OsStr::from_bytes(b"public/\xff"));
+        assert!(matches!(PgName::from_path(path), Err(IdentError::PathComponentError(_))));
+    }
+
+    #[test]
+    fn from_delimited() {
+        let id = PgName::from_delimited("public:users", ':').unwrap();
+        assert!(matches!(id, PgName::IdPair(_, _)));
+        assert_eq!(format!("{}", id), "public.users");
+
+        let id = PgName::from_delimited("a/B/c d", '/').unwrap();
+        assert!(matches!(id, PgName::Namespaced(_)));
+        assert_eq!(format!("{}", id), "a.\"B\".\"c d\"");
+
+        assert!(matches!(PgName::from_delimited("a//b", '/'), Err(IdentError::ZeroLengthError())));
+        assert!(matches!(PgName::from_delimited("a/", '/'), Err(IdentError::ZeroLengthError())));
+    }
+
+    #[test]
+    fn new_ns_pair() {
+        let id = PgName::new_ns(vec!["public", "My Table"]).unwrap();
+        let PgName::IdPair(schema, table) = &id else {
+            panic!("expected a pair, got {:?}", id);
+        };
+        assert!(matches!(schema, PgIdent::Id("public")));
+        assert!(matches!(table, PgIdent::Quoted(t) if t == "My Table"));
+        assert_eq!(format!("{}", id), "public.\"My Table\"");
+    }
+
+    #[test]
+    fn postgres_equality() {
+        use std::collections::HashMap;
+
+        let a = PgName::new_ns(vec!["public", "users"]).unwrap();
+        let b: PgName<&str> = PgName::IdPair(PgIdent::Quoted("public".to_string()), PgIdent::Id("Users"));
+        let c = PgName::new_ns(vec!["public", "Users"]).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(a, PgName::new("users").unwrap());
+        assert_ne!(
+            PgName::new_ns(vec!["a", "b", "c"]).unwrap(),
+            PgName::Namespaced(vec![PgIdent::Id("a"), PgIdent::Id("b")])
+        );
+
+        let mut seen = HashMap::new();
+        for name in [a, b, c] {
+            *seen.entry(name).or_insert(0) += 1;
+        }
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[&PgName::new_ns(vec!["public", "users"]).unwrap()], 2);
+    }
+
     #[test]
     fn simple() {
         let id = PgName::new("foo").unwrap();
@@ -164,6 +1066,33 @@ mod tests {
         assert_eq!(format!("{}", id), "\"The \"\"table\"\"\"");
     }
 
+    #[test]
+    fn optional_schema() {
+        let id = PgName::from_optional_schema(Some("public"), "users").unwrap();
+        assert!(matches!(id, PgName::IdPair(_, _)));
+        assert_eq!(format!("{}", id), "public.users");
+        let id = PgName::from_optional_schema(None, "Users").unwrap();
+        assert!(matches!(id, PgName::Id(_)));
+        assert_eq!(format!("{}", id), "\"Users\"");
+        assert!(PgName::from_optional_schema(Some("a\x00"), "users").is_err());
+    }
+
+    #[test]
+    fn static_schema() {
+        static MY_EXT: PgIdent<&str> = PgIdent::Id("my_ext");
+        let names: Vec<PgName<String>> = (0..100)
+            .map(|i| PgName::in_static_schema(&MY_EXT, format!("Table{}", i)).unwrap())
+            .collect();
+        assert_eq!(names.len(), 100);
+        assert_eq!(names[0].to_string(), "my_ext.\"Table0\"");
+        assert_eq!(names[99].to_string(), "my_ext.\"Table99\"");
+
+        let quoted: &'static PgIdent<&str> = Box::leak(Box::new(PgIdent::new("My Ext").unwrap()));
+        let id = PgName::<&str>::in_static_schema(quoted, "users").unwrap();
+        assert_eq!(id.to_string(), "\"My Ext\".users");
+        assert!(PgName::<&str>::in_static_schema(&MY_EXT, "").is_err());
+    }
+
     #[test]
     fn test_name_id() {
         let id = PgName::new("foo").unwrap();
@@ -182,4 +1111,507 @@ mod tests {
         assert_eq!(format!("{}", id.name()), "c");
     }
 
+    #[test]
+    fn test_replace_schema() {
+        let id = PgName::new_ns(vec!["public", "My Table"]).unwrap();
+        let moved = id.replace_schema("archive").unwrap();
+        assert_eq!(format!("{}", moved), "archive.\"My Table\"");
+
+        let quoted: PgName<&str> = PgName::IdPair(PgIdent::Id("public"), PgIdent::Quoted("users".to_string()));
+        let moved = quoted.replace_schema("archive").unwrap();
+        assert!(matches!(moved.name(), PgIdent::Quoted(_)));
+        assert_eq!(format!("{}", moved), "archive.\"users\"");
+
+        let id = PgName::new("users").unwrap();
+        assert_eq!(format!("{}", id.replace_schema("archive").unwrap()), "archive.users");
+        let id = PgName::new_ns(vec!["db", "public", "users"]).unwrap();
+        assert_eq!(format!("{}", id.replace_schema("Archive").unwrap()), "db.\"Archive\".users");
+
+        let short = PgName::Namespaced(vec![PgIdent::Id("users")]);
+        assert_eq!(format!("{}", short.replace_schema("archive").unwrap()), "archive.users");
+        let column = PgName::new_ns(vec!["db", "public", "users", "id"]).unwrap();
+        assert!(matches!(column.replace_schema("archive"), Err(IdentError::PartCountError { expected: 3, actual: 4 })));
+    }
+
+    #[test]
+    fn test_with_catalog() {
+        let id: PgName<String> = ("public", "users").try_into().unwrap();
+        let id = id.with_catalog("db".to_string()).unwrap();
+        assert!(matches!(id, PgName::Namespaced(_)));
+        assert_eq!(format!("{}", id), "db.public.users");
+    }
+
+    #[test]
+    fn test_with_catalog_arity() {
+        let id = PgName::new_ns(vec!["db", "public", "users"]).unwrap();
+        assert!(matches!(
+            id.with_catalog("other"),
+            Err(IdentError::PartCountError { expected: 2, actual: 3 })
+        ));
+        let id = PgName::new("users").unwrap();
+        assert!(id.with_catalog("db").is_err());
+    }
+
+    #[test]
+    fn dynamic_sql_embedded() {
+        let id = PgName::new_ns(vec!["public", "User"]).unwrap();
+        assert_eq!(
+            format!("EXECUTE 'SELECT * FROM {}'", id.to_dynamic_sql_embedded()),
+            "EXECUTE 'SELECT * FROM public.\"User\"'"
+        );
+        let id = PgName::new_ns(vec!["public", "Bob's \"table\""]).unwrap();
+        assert_eq!(id.to_dynamic_sql_embedded(), "public.\"Bob''s \"\"table\"\"\"");
+    }
+
+    #[test]
+    fn test_minimal_reference() {
+        let known: Vec<PgName<&str>> = vec![
+            PgName::new_ns(vec!["public", "users"]).unwrap(),
+            PgName::new_ns(vec!["audit", "users"]).unwrap(),
+            PgName::new_ns(vec!["public", "orders"]).unwrap(),
+        ];
+        assert_eq!(format!("{}", known[0].minimal_reference(&known)), "public.users");
+        assert_eq!(format!("{}", known[1].minimal_reference(&known)), "audit.users");
+        assert_eq!(format!("{}", known[2].minimal_reference(&known)), "orders");
+
+        let other = PgName::new_ns(vec!["other", "orders"]).unwrap();
+        assert_eq!(format!("{}", other.minimal_reference(&known)), "other.orders");
+    }
+
+    #[test]
+    fn test_render() {
+        let id = PgName::new("foo").unwrap();
+        assert!(matches!(id.render(), Cow::Borrowed("foo")));
+        let id = PgName::new("Foo").unwrap();
+        assert!(matches!(id.render(), Cow::Owned(_)));
+        assert_eq!(id.render(), "\"Foo\"");
+        let id = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert_eq!(id.render(), "public.users");
+    }
+
+    #[test]
+    fn test_to_sql() {
+        let names: Vec<PgName<&str>> = vec![
+            PgName::new("foo").unwrap(),
+            PgName::new_ns(vec!["public", "My \"Table\""]).unwrap(),
+            PgName::new_ns(vec!["a", "B", "c"]).unwrap(),
+        ];
+        for id in names {
+            assert_eq!(id.to_sql(), id.to_string());
+            assert_eq!(id.rendered_len(), id.to_string().len());
+        }
+    }
+
+    #[test]
+    fn diff_schema_only() {
+        let a = PgName::new_ns(vec!["public", "users"]).unwrap();
+        let b = PgName::new_ns(vec!["audit", "users"]).unwrap();
+        let diff = a.diff(&b);
+        assert_eq!(diff, NameDiff { schema_changed: true, relation_changed: false, quoting_only: false });
+        assert!(!diff.is_same_object());
+
+        let c = PgName::new("users").unwrap();
+        assert!(a.diff(&c).schema_changed);
+        assert!(!a.diff(&a).schema_changed);
+    }
+
+    #[test]
+    fn diff_quoting_only() {
+        let a: PgName<&str> = PgName::IdPair(PgIdent::Id("public"), PgIdent::Id("users"));
+        let b: PgName<&str> = PgName::IdPair(PgIdent::Id("public"), PgIdent::Quoted("users".to_string()));
+        let diff = a.diff(&b);
+        assert_eq!(diff, NameDiff { schema_changed: false, relation_changed: false, quoting_only: true });
+        assert!(diff.is_same_object());
+
+        let c = PgName::new_ns(vec!["public", "Users"]).unwrap();
+        assert_eq!(a.diff(&c), NameDiff { schema_changed: false, relation_changed: true, quoting_only: false });
+    }
+
+    #[test]
+    fn test_into_ident() {
+        let id = PgName::new("Users").unwrap().into_ident().unwrap();
+        assert_eq!(format!("{}", id), "\"Users\"");
+
+        let qualified = PgName::new_ns(vec!["public", "users"]).unwrap();
+        let back = qualified.into_ident().unwrap_err();
+        assert_eq!(format!("{}", back), "public.users");
+    }
+
+    #[test]
+    fn test_catalog_keys() {
+        let id = PgName::parse("Public.\"My \"\"Table\"\"\"").unwrap();
+        assert_eq!(
+            id.catalog_keys(),
+            CatalogKeys { schema: Some("public".to_string()), relation: "My \"Table\"".to_string() }
+        );
+        let id = PgName::new("Users").unwrap();
+        assert_eq!(id.catalog_keys(), CatalogKeys { schema: None, relation: "Users".to_string() });
+    }
+
+    #[test]
+    fn test_as_values() {
+        let id = PgName::new_ns(vec!["public", "The \"table\""]).unwrap();
+        assert_eq!(id.as_values(), vec!["public", "The \"table\""]);
+        let again = PgName::new_ns(id.as_values()).unwrap();
+        assert_eq!(again.to_string(), id.to_string());
+    }
+
+    #[test]
+    fn test_first_last() {
+        let id = PgName::new("users").unwrap();
+        assert_eq!(format!("{} {}", id.first(), id.last()), "users users");
+        let id = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert_eq!(format!("{} {}", id.first(), id.last()), "public users");
+        let id = PgName::new_ns(vec!["db", "public", "users"]).unwrap();
+        assert_eq!(format!("{} {}", id.first(), id.last()), "db users");
+    }
+
+    #[test]
+    fn test_catalog_schema_relation() {
+        let id = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert!(id.catalog().is_none());
+        assert_eq!(id.schema().unwrap().to_string(), "public");
+        assert_eq!(id.relation().to_string(), "users");
+
+        let id = PgName::new_ns(vec!["db", "public", "users"]).unwrap();
+        assert_eq!(id.catalog().unwrap().to_string(), "db");
+        assert_eq!(id.schema().unwrap().to_string(), "public");
+        assert_eq!(id.relation().to_string(), "users");
+
+        let id = PgName::new("users").unwrap();
+        assert!(id.catalog().is_none());
+        assert!(id.schema().is_none());
+        assert_eq!(id.relation().to_string(), "users");
+    }
+
+    #[test]
+    fn test_to_array() {
+        let id = PgName::new("users").unwrap();
+        let (parts, count) = id.to_array();
+        assert_eq!(count, 1);
+        assert!(matches!(parts, [Some(PgIdent::Id("users")), None, None]));
+
+        let id = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert!(matches!(id.to_array(), ([Some(PgIdent::Id("public")), Some(PgIdent::Id("users")), None], 2)));
+
+        let id = PgName::new_ns(vec!["db", "public", "users"]).unwrap();
+        assert!(matches!(id.to_array(), ([Some(PgIdent::Id("db")), Some(_), Some(PgIdent::Id("users"))], 3)));
+
+        let id = PgName::new_ns(vec!["a", "b", "c", "d"]).unwrap();
+        assert!(matches!(id.to_array(), ([None, None, None], 4)));
+    }
+
+    #[test]
+    fn eq_default_schema() {
+        let public = PgIdent::new("public").unwrap();
+        let bare = PgName::new("users").unwrap();
+        let qualified = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert!(bare.eq_with_default_schema(&qualified, &public));
+        assert!(qualified.eq_with_default_schema(&bare, &public));
+        assert!(bare.eq_with_default_schema(&bare, &public));
+
+        let audit = PgIdent::new("audit").unwrap();
+        assert!(!bare.eq_with_default_schema(&qualified, &audit));
+        let other = PgName::new_ns(vec!["audit", "users"]).unwrap();
+        assert!(bare.eq_with_default_schema(&other, &audit));
+        assert!(!qualified.eq_with_default_schema(&other, &audit));
+    }
+
+    #[test]
+    fn potentially_ambiguous() {
+        let id = PgName::new("users").unwrap();
+        assert!(id.is_potentially_ambiguous(2));
+        assert!(!id.is_potentially_ambiguous(1));
+        let id = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert!(!id.is_potentially_ambiguous(2));
+    }
+
+    #[test]
+    fn with_schema() {
+        let id = PgName::new("users").unwrap().with_schema("App").unwrap();
+        assert!(matches!(id, PgName::IdPair(_, _)));
+        assert_eq!(id.to_string(), "\"App\".users");
+        let id = id.with_schema("db").unwrap();
+        assert!(matches!(id, PgName::Namespaced(_)));
+        assert_eq!(id.to_string(), "db.\"App\".users");
+        assert_eq!(id.with_schema("x").unwrap().to_string(), "x.db.\"App\".users");
+        assert!(id.with_schema("").is_err());
+    }
+
+    #[test]
+    fn qualify() {
+        let id = PgName::new("users").unwrap().qualify("Email").unwrap();
+        assert_eq!(id.to_string(), "users.\"Email\"");
+        let id = PgName::new_ns(vec!["app", "users"]).unwrap().qualify("email").unwrap();
+        assert!(matches!(id, PgName::Namespaced(_)));
+        assert_eq!(id.to_string(), "app.users.email");
+        assert_eq!(id.qualify("x y").unwrap().to_string(), "app.users.email.\"x y\"");
+        assert!(id.qualify("a\x00").is_err());
+    }
+
+    #[test]
+    fn render_into() {
+        let mut buf = String::new();
+        PgName::new_ns(vec!["app", "users"]).unwrap().render_into(&mut buf);
+        buf.push_str(", ");
+        PgName::new("My Table").unwrap().render_into(&mut buf);
+        assert_eq!(buf, "app.users, \"My Table\"");
+    }
+
+    #[test]
+    fn bare_after_fold() {
+        assert_eq!(PgName::new("Users").unwrap().would_be_bare_after_fold(), vec![true]);
+        assert_eq!(PgName::new("My Table").unwrap().would_be_bare_after_fold(), vec![false]);
+        let id = PgName::new_ns(vec!["public", "Users", "Select"]).unwrap();
+        assert_eq!(id.would_be_bare_after_fold(), vec![false, true, false]);
+    }
+
+    #[test]
+    fn render_annotated() {
+        let id = PgName::new_ns(vec!["public", "Users"]).unwrap();
+        assert_eq!(id.render_annotated(), "public.\"Users\"  -- quoted: case");
+        let id = PgName::new_ns(vec!["My Schema", "select"]).unwrap();
+        assert_eq!(id.render_annotated(), "\"My Schema\".\"select\"  -- quoted: character (\"My Schema\"), keyword (\"select\")");
+        assert_eq!(PgName::new("users").unwrap().render_annotated(), "users");
+    }
+
+    #[test]
+    fn logical_display() {
+        let id = PgName::parse("public.\"My Table\"").unwrap();
+        assert_eq!(id.to_logical_display(), "public.My Table");
+        let id = PgName::new_ns(vec!["a.b", "Say \"hi\""]).unwrap();
+        assert_eq!(id.to_logical_display(), "a.b.Say \"hi\"");
+    }
+
+    #[test]
+    fn eq_parts() {
+        let id = PgName::parse("public.\"Users\"").unwrap();
+        assert!(id == vec!["public", "Users"]);
+        assert!(id != vec!["public", "users"]);
+        assert!(id != vec!["Users"]);
+        assert!(id == &["public", "Users"][..]);
+        let id = PgName::parse("PUBLIC.users").unwrap();
+        assert!(id == vec!["public", "users"]);
+        assert!(id != vec!["db", "public", "users"]);
+    }
+
+    #[test]
+    fn safe_for_interpolation() {
+        let id = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert_eq!(id.assert_safe_for_interpolation().unwrap(), "public.users");
+        let id = PgName::new_ns(vec!["app", "x\"; DROP TABLE users; --"]).unwrap();
+        assert_eq!(id.assert_safe_for_interpolation().unwrap(), "app.\"x\"\"; DROP TABLE users; --\"");
+
+        // Only reachable by building the variant directly, without escaping
+        let id: PgName<&str> = PgName::Id(PgIdent::Quoted("x\"; DROP TABLE users; --".to_string()));
+        assert!(matches!(id.assert_safe_for_interpolation(), Err(IdentError::UnsafeRenderingError(_))));
+        let id: PgName<&str> = PgName::Id(PgIdent::Quoted("a\"\"\"b".to_string()));
+        assert!(id.assert_safe_for_interpolation().is_err());
+        let id: PgName<&str> = PgName::Id(PgIdent::Id("x; drop"));
+        assert!(id.assert_safe_for_interpolation().is_err());
+
+        // Not tied to the default length limit
+        let long = PgIdent::new_with_limit("a".repeat(100), 127).unwrap();
+        let quoted = PgIdent::new_with_limit("A\"".repeat(50), 127).unwrap();
+        let id = PgName::IdPair(long, quoted);
+        assert_eq!(id.assert_safe_for_interpolation().unwrap(), id.to_string());
+    }
+
+    #[test]
+    fn two_level() {
+        let public = PgIdent::new("public").unwrap();
+        let (schema, relation) = PgName::new("users").unwrap().to_two_level(&public).unwrap();
+        assert_eq!((schema.to_string(), relation.to_string()), ("public".to_string(), "users".to_string()));
+        let (schema, relation) = PgName::new_ns(vec!["App", "users"]).unwrap().to_two_level(&public).unwrap();
+        assert_eq!((schema.to_string(), relation.to_string()), ("\"App\"".to_string(), "users".to_string()));
+        let (schema, relation) = PgName::new_ns(vec!["db", "app", "users"]).unwrap().to_two_level(&public).unwrap();
+        assert_eq!((schema.to_string(), relation.to_string()), ("app".to_string(), "users".to_string()));
+
+        let column = PgName::new_ns(vec!["db", "app", "users", "id"]).unwrap();
+        assert!(matches!(column.to_two_level(&public), Err(IdentError::PartCountError { expected: 3, actual: 4 })));
+        let (schema, relation) = PgName::Namespaced(vec![PgIdent::Id("users")]).to_two_level(&public).unwrap();
+        assert_eq!((schema.to_string(), relation.to_string()), ("public".to_string(), "users".to_string()));
+    }
+
+    #[test]
+    fn source_quoting() {
+        let parsed = PgName::parse_with_source_quoting("public.\"Users\"").unwrap();
+        assert_eq!(parsed.source_quoting(), [false, true]);
+        assert_eq!(parsed.name, ("public", "Users"));
+        let parsed = PgName::parse_with_source_quoting("\"public\".USERS").unwrap();
+        assert_eq!(parsed.source_quoting(), [true, false]);
+        // Bare parts that are quoted when rendered still weren't quoted in the source
+        let parsed = PgName::parse_with_source_quoting("日本.Équipe.MyTable").unwrap();
+        assert_eq!(parsed.source_quoting(), [false, false, false]);
+        assert_eq!(parsed.name.to_string(), "\"日本\".\"Équipe\".mytable");
+        assert!(PgName::parse_with_source_quoting("public.").is_err());
+    }
+
+    #[test]
+    fn starts_with() {
+        let id = PgName::new_ns(vec!["public", "app_users"]).unwrap();
+        assert!(id.relation_starts_with("app_"));
+        assert!(id.relation_starts_with("APP"));
+        assert!(!id.relation_starts_with("users"));
+        assert!(id.schema_starts_with("pub"));
+        assert!(!id.schema_starts_with("app"));
+        assert!(!PgName::new("App_users").unwrap().relation_starts_with("app"));
+        assert!(!PgName::new("app_users").unwrap().schema_starts_with(""));
+    }
+
+    #[test]
+    fn fold_report() {
+        let quoted = PgName::parse("public.\"Users\"").unwrap();
+        let bare = PgName::parse("public.Users").unwrap();
+        assert_eq!(quoted.fold_report(), "public value=\"public\" key=\"public\"\n\"Users\" value=\"Users\" key=\"Users\"");
+        assert_eq!(bare.fold_report(), "public value=\"public\" key=\"public\"\nusers value=\"users\" key=\"users\"");
+        assert_ne!(quoted, bare);
+    }
+
+    #[test]
+    fn statement_terminator() {
+        assert!(PgName::parse("public.\"a;b\"").unwrap().contains_statement_terminator());
+        assert!(!PgName::new_ns(vec!["public", "users"]).unwrap().contains_statement_terminator());
+    }
+
+    #[test]
+    fn conventional() {
+        assert!(PgName::new_ns(vec!["public", "users"]).unwrap().is_conventional());
+        assert!(PgName::new("users").unwrap().is_conventional());
+        assert!(!PgName::new_ns(vec!["public", "Users"]).unwrap().is_conventional());
+        assert!(!PgName::new_ns(vec!["public", "order"]).unwrap().is_conventional());
+        assert!(!PgName::parse("public.\"users\"").unwrap().is_conventional());
+    }
+
+    #[test]
+    fn test_is_bare_single() {
+        assert!(PgName::new("foo").unwrap().is_bare_single());
+        assert!(!PgName::new("Foo").unwrap().is_bare_single());
+        assert!(!PgName::new_ns(vec!["public", "users"]).unwrap().is_bare_single());
+    }
+
+    #[test]
+    fn wrapped() {
+        let id = PgName::new_ns(vec!["aaaa", "bbbb", "cccc"]).unwrap();
+        assert_eq!(id.render_wrapped(10, "\n"), "aaaa.bbbb.\ncccc");
+        assert_eq!(id.render_wrapped(5, "\n"), "aaaa.\nbbbb.\ncccc");
+        assert_eq!(id.render_wrapped(2, "\n"), "aaaa.\nbbbb.\ncccc");
+        assert_eq!(id.render_wrapped(80, "\n"), "aaaa.bbbb.cccc");
+        // The indent counts towards the width
+        assert_eq!(id.render_wrapped(10, "\\\n  "), "aaaa.bbbb.\\\n  cccc");
+        assert_eq!(id.render_wrapped(9, "\n    "), "aaaa.\n    bbbb.\n    cccc");
+
+        // Width is in characters, not bytes
+        let id = PgName::new_ns(vec!["éééé", "éééé"]).unwrap();
+        assert_eq!(id.render_wrapped(9, "\n"), "éééé.éééé");
+    }
+
+    #[test]
+    fn test_force_qualified() {
+        let public = PgIdent::new("public").unwrap();
+        let id = PgName::new("users").unwrap().force_qualified(&public);
+        assert!(matches!(id, PgName::IdPair(_, _)));
+        assert_eq!(format!("{}", id), "public.users");
+        let id = PgName::new_ns(vec!["audit", "users"]).unwrap().force_qualified(&public);
+        assert_eq!(format!("{}", id), "audit.users");
+    }
+
+    #[test]
+    fn test_retain_parts() {
+        let id = PgName::new_ns(vec!["db", "public", "users"]).unwrap();
+        let id = id.retain_parts(|i, _| i > 0).unwrap();
+        assert!(matches!(id, PgName::IdPair(_, _)));
+        assert_eq!(format!("{}", id), "public.users");
+
+        let id = id.retain_parts(|_, part| part.to_string() != "public").unwrap();
+        assert!(matches!(id, PgName::Id(_)));
+        assert!(matches!(id.retain_parts(|_, _| false), Err(IdentError::ZeroLengthError())));
+    }
+
+    #[test]
+    fn test_simplify() {
+        let public = PgIdent::new("public").unwrap();
+        let id = PgName::new_ns(vec!["public", "users"]).unwrap();
+        let simplified = id.simplify(&public);
+        assert!(matches!(simplified, PgName::Id(_)));
+        assert_eq!(format!("{}", simplified), "users");
+
+        let id = PgName::new_ns(vec!["analytics", "users"]).unwrap();
+        assert_eq!(format!("{}", id.simplify(&public)), "analytics.users");
+        let id = PgName::new("users").unwrap();
+        assert_eq!(format!("{}", id.simplify(&public)), "users");
+    }
+
+    #[test]
+    fn json_escaped() {
+        let id = PgName::new_ns(vec!["public", "The \"table\""]).unwrap();
+        assert_eq!(id.to_json_escaped(), r#"public.\"The \"\"table\"\"\""#);
+        let id = PgName::new("a\\b\nc").unwrap();
+        assert_eq!(id.to_json_escaped(), r#"\"a\\b\nc\""#);
+        let id = PgName::new("a\x01").unwrap();
+        assert_eq!(id.to_json_escaped(), r#"\"a\u0001\""#);
+    }
+
+    #[test]
+    fn creation_order() {
+        let mut names: Vec<PgName<&str>> = vec![
+            PgName::new_ns(vec!["public", "users"]).unwrap(),
+            PgName::new_ns(vec!["db", "app", "events"]).unwrap(),
+            PgName::new("public").unwrap(),
+            PgName::new_ns(vec!["app", "events"]).unwrap(),
+            PgName::new("app").unwrap(),
+        ];
+        names.sort_by_key(|name| name.creation_sort_key());
+        let sorted: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        assert_eq!(sorted, vec!["app", "public", "app.events", "public.users", "db.app.events"]);
+    }
+
+    #[test]
+    fn test_debug_label() {
+        let id = PgName::new_ns(vec!["db", "public", "My Table"]).unwrap();
+        assert_eq!(id.debug_label(), "cat:db/ns:public/rel:My Table");
+        let id = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert_eq!(id.debug_label(), "ns:public/rel:users");
+        assert_eq!(PgName::new("users").unwrap().debug_label(), "rel:users");
+    }
+
+    #[test]
+    fn only_modifier() {
+        let id = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert_eq!(id.with_only_modifier(), "ONLY public.users");
+        let id = PgName::new("My Table").unwrap();
+        assert_eq!(id.with_only_modifier(), "ONLY \"My Table\"");
+    }
+
+    #[test]
+    fn regclass_text() {
+        let id = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert!(id.equals_regclass_text("users"));
+        assert!(id.equals_regclass_text("public.users"));
+        assert!(id.equals_regclass_text("\"public\".\"users\""));
+        assert!(!id.equals_regclass_text("audit.users"));
+        assert!(!id.equals_regclass_text("\"Users\""));
+        assert!(!id.equals_regclass_text("db.public.users"));
+        assert!(!id.equals_regclass_text("public.users;"));
+
+        let id = PgName::new_ns(vec!["App", "My Table"]).unwrap();
+        assert!(id.equals_regclass_text("\"App\".\"My Table\""));
+        assert!(id.equals_regclass_text("\"My Table\""));
+        assert!(!id.equals_regclass_text("app.\"My Table\""));
+    }
+
+    #[test]
+    fn tool_canonical_folds() {
+        let id = PgName::new("Foo").unwrap();
+        assert_eq!(id.render_tool_canonical(), "foo");
+    }
+
+    #[test]
+    fn tool_canonical_quotes_upper() {
+        let id = PgName::new("My Table").unwrap();
+        assert_eq!(id.render_tool_canonical(), "\"MY TABLE\"");
+        let id = PgName::new_ns(vec!["public", "My Table"]).unwrap();
+        assert_eq!(id.render_tool_canonical(), "public.\"MY TABLE\"");
+    }
+
 }
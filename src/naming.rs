@@ -0,0 +1,91 @@
+use crate::PgIdent;
+use crate::util::{clip, MAX_IDENT_BYTES};
+
+// Predicts the name Postgres generates for an index created without an explicit name,
+// e.g. `CREATE INDEX ON users (email)` -> `users_email_idx`.
+// This follows ChooseIndexNameAddition and makeObjectName in the Postgres source, but does
+// not account for conflicts with existing objects (where Postgres appends a number).
+// `suffix` is the label, e.g. `idx`, `key` or `pkey`.
+pub fn default_index_name<T: AsRef<str>>(table: &PgIdent<T>, cols: &[PgIdent<T>], suffix: &str) -> PgIdent<String> {
+    let table = table.as_value();
+    let mut cols_name = String::new();
+    for col in cols {
+        if !cols_name.is_empty() {
+            cols_name.push('_');
+        }
+        cols_name.push_str(&col.as_value());
+        if cols_name.len() >= MAX_IDENT_BYTES {
+            break;
+        }
+    }
+    let cols_name = clip(&cols_name, MAX_IDENT_BYTES);
+
+    let mut overhead = 0;
+    if !cols_name.is_empty() {
+        overhead += 1;
+    }
+    if !suffix.is_empty() {
+        overhead += suffix.len() + 1;
+    }
+    let available = MAX_IDENT_BYTES.saturating_sub(overhead);
+
+    // Preferentially truncate the longer of the two names
+    let (mut table_len, mut cols_len) = (table.len(), cols_name.len());
+    while table_len + cols_len > available {
+        if table_len > cols_len {
+            table_len -= 1;
+        } else {
+            cols_len -= 1;
+        }
+    }
+
+    let mut name = clip(&table, table_len).to_string();
+    if !cols_name.is_empty() {
+        name.push('_');
+        name.push_str(clip(cols_name, cols_len));
+    }
+    if !suffix.is_empty() {
+        name.push('_');
+        name.push_str(suffix);
+    }
+    PgIdent::from_trusted(name.replace('\x00', ""))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_index_name() {
+        let table = PgIdent::new("users").unwrap();
+        let cols = vec![PgIdent::new("email").unwrap(), PgIdent::new("Org").unwrap()];
+        assert_eq!(default_index_name(&table, &cols, "idx").to_string(), "\"users_email_Org_idx\"");
+        assert_eq!(default_index_name(&table, &cols[..1], "key").to_string(), "users_email_key");
+    }
+
+    #[test]
+    fn truncated_index_name() {
+        // CREATE INDEX ON customer_order_line_items_archive_2023 (customer_identifier, order_line_item_identifier);
+        // is named by Postgres 16 as below: the 38-byte table name is trimmed to 29 bytes, the
+        // 46-byte column list to 29 bytes, leaving room for the underscores and the label.
+        let table = PgIdent::new("customer_order_line_items_archive_2023").unwrap();
+        let cols = vec![
+            PgIdent::new("customer_identifier").unwrap(),
+            PgIdent::new("order_line_item_identifier").unwrap(),
+        ];
+        let name = default_index_name(&table, &cols, "idx");
+        assert_eq!(name.to_string(), "customer_order_line_items_arc_customer_identifier_order_lin_idx");
+        assert_eq!(name.to_string().len(), 63);
+    }
+
+    #[test]
+    fn truncates_on_char_boundary() {
+        let table = PgIdent::new("é".repeat(31)).unwrap();
+        let cols = vec![PgIdent::new("col".to_string()).unwrap()];
+        let name = default_index_name(&table, &cols, "pkey").to_string();
+        assert!(name.len() <= 63);
+        assert!(name.ends_with("é_col_pkey"));
+    }
+
+}
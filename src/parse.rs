@@ -0,0 +1,130 @@
+use crate::{IdentError, PgIdent};
+use crate::keywords::is_reserved;
+use crate::util::{fold, is_ident_char, is_ident_start, unescape, MAX_IDENT_BYTES};
+
+// A cursor over SQL text that reads identifier tokens, the inverse of the `Display` impls.
+pub struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    pub fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    // Consumes `word` if it appears next as a whole token
+    pub fn eat_word(&mut self, word: &str) -> bool {
+        let rest = &self.input[self.pos..];
+        if rest.starts_with(word) && !rest[word.len()..].starts_with(is_ident_char) {
+            self.pos += word.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    pub fn unexpected(&self) -> IdentError {
+        match self.peek() {
+            Some(ch) => IdentError::UnexpectedCharError { ch, offset: self.pos },
+            None => IdentError::ZeroLengthError(),
+        }
+    }
+
+    // Reads a dotted name such as `public."Users"`
+    pub fn name(&mut self) -> Result<Vec<PgIdent<String>>, IdentError> {
+        self.name_with(false)
+    }
+
+    // With `collapse_dots`, a run of dots between two parts is read as one, so `public..users`
+    // is `public.users`. Leading and trailing dots are still an error.
+    pub fn name_with(&mut self, collapse_dots: bool) -> Result<Vec<PgIdent<String>>, IdentError> {
+        Ok(self.name_with_quoting(collapse_dots)?.0)
+    }
+
+    // Also returns which parts were written in double quotes
+    pub fn name_with_quoting(&mut self, collapse_dots: bool) -> Result<(Vec<PgIdent<String>>, Vec<bool>), IdentError> {
+        let mut ids = Vec::new();
+        let mut quoted = Vec::new();
+        loop {
+            match self.peek() {
+                None if ids.is_empty() => return Err(IdentError::ZeroLengthError()),
+                None | Some('.') => return Err(IdentError::EmptySegment { index: ids.len() }),
+                c => quoted.push(c == Some('"')),
+            }
+            ids.push(self.ident()?);
+            if !self.eat('.') {
+                return Ok((ids, quoted));
+            }
+            while collapse_dots && self.eat('.') {}
+        }
+    }
+
+    // Reads one bare or double-quoted identifier. Bare identifiers are folded to lower-case
+    // as Postgres would, quoted identifiers keep their quoting.
+    pub fn ident(&mut self) -> Result<PgIdent<String>, IdentError> {
+        let start = self.pos;
+        match self.peek() {
+            Some('"') => {
+                let bytes = self.input.as_bytes();
+                let mut i = start + 1;
+                loop {
+                    match bytes.get(i) {
+                        None => return Err(IdentError::UnterminatedQuoteError()),
+                        Some(b'"') if bytes.get(i + 1) == Some(&b'"') => i += 2,
+                        Some(b'"') => break,
+                        Some(_) => i += 1,
+                    }
+                }
+                self.pos = i + 1;
+                let inner = &self.input[start + 1..i];
+                if inner.is_empty() {
+                    return Err(IdentError::ZeroLengthError());
+                }
+                if inner.contains('\x00') {
+                    return Err(IdentError::NullByteError());
+                }
+                let bytes = unescape(inner).len();
+                if bytes > MAX_IDENT_BYTES {
+                    return Err(IdentError::TooLong { bytes });
+                }
+                Ok(PgIdent::Quoted(inner.to_string()))
+            }
+            Some(c) if is_ident_start(c) => {
+                while let Some(c) = self.peek().filter(|c| is_ident_char(*c)) {
+                    self.pos += c.len_utf8();
+                }
+                // Postgres treats a bare reserved word as the key word, not a name
+                let word = fold(&self.input[start..self.pos]).into_owned();
+                if is_reserved(&word) {
+                    return Err(IdentError::ReservedKeyword { word, offset: start });
+                }
+                PgIdent::new(word)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+}
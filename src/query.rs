@@ -0,0 +1,120 @@
+use crate::{IdentError, PgIdent, PgName};
+use crate::util::quote_literal;
+
+// Renders schema-qualified names as a list of `('schema','relation')` literal tuples, for use in
+// queries like `WHERE (table_schema, table_name) IN (...)`.
+// The literals hold the values as stored in the catalogs, and every name must have exactly two parts.
+pub fn names_to_pair_tuples<T: AsRef<str>>(names: &[PgName<T>]) -> Result<String, IdentError> {
+    let mut tuples = Vec::with_capacity(names.len());
+    for name in names {
+        let PgName::IdPair(schema, table) = name else {
+            return Err(IdentError::PartCountError { expected: 2, actual: name.parts().len() });
+        };
+        tuples.push(format!("({},{})", quote_literal(&schema.folded()), quote_literal(&table.folded())));
+    }
+    Ok(tuples.join(","))
+}
+
+// e.g. `GRANT SELECT, INSERT ON public.users TO "App Role", admin`.
+// The privileges are written as given, so they should be constants, never user input.
+// Empty privilege or role lists are an error, since they'd render invalid SQL.
+pub fn grant_statement<T: AsRef<str>>(privs: &[&str], object: &PgName<T>, roles: &[PgIdent<T>]) -> Result<String, IdentError> {
+    check_privileges(privs, roles)?;
+    Ok(format!("GRANT {} ON {} TO {}", privs.join(", "), object, join_idents(roles)))
+}
+
+// The inverse of `grant_statement`, e.g. `REVOKE SELECT ON public.users FROM "App Role"`
+pub fn revoke_statement<T: AsRef<str>>(privs: &[&str], object: &PgName<T>, roles: &[PgIdent<T>]) -> Result<String, IdentError> {
+    check_privileges(privs, roles)?;
+    Ok(format!("REVOKE {} ON {} FROM {}", privs.join(", "), object, join_idents(roles)))
+}
+
+fn check_privileges<T: AsRef<str>>(privs: &[&str], roles: &[PgIdent<T>]) -> Result<(), IdentError> {
+    if privs.is_empty() {
+        return Err(IdentError::EmptyListError("privilege"));
+    }
+    if roles.is_empty() {
+        return Err(IdentError::EmptyListError("role"));
+    }
+    Ok(())
+}
+
+// e.g. `COPY public.users (id, "Full Name") TO STDOUT` for exports, or `... FROM STDIN`
+// for imports when `to_stdout` is false. With no columns, the column list is left out.
+pub fn copy_statement<T: AsRef<str>>(name: &PgName<T>, columns: &[PgIdent<T>], to_stdout: bool) -> String {
+    let mut sql = format!("COPY {}", name);
+    if !columns.is_empty() {
+        sql.push_str(&format!(" ({})", join_idents(columns)));
+    }
+    sql.push_str(if to_stdout { " TO STDOUT" } else { " FROM STDIN" });
+    sql
+}
+
+// e.g. `ALTER TABLE public.users RENAME TO customers`. The new name is a single identifier,
+// since `RENAME TO` never moves an object to another schema. `kind` is written as given.
+pub fn rename_statement<T: AsRef<str>>(kind: &str, from: &PgName<T>, to: &PgIdent<T>) -> String {
+    format!("ALTER {} {} RENAME TO {}", kind, from, to)
+}
+
+fn join_idents<T: AsRef<str>>(ids: &[PgIdent<T>]) -> String {
+    ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pair_tuples() {
+        let names: Vec<PgName<String>> = vec![
+            ("public", "users").try_into().unwrap(),
+            ("other", "Bob's Table").try_into().unwrap(),
+        ];
+        assert_eq!(names_to_pair_tuples(&names).unwrap(), "('public','users'),('other','Bob''s Table')");
+    }
+
+    #[test]
+    fn pair_tuples_unqualified() {
+        let names = vec![PgName::new("users").unwrap()];
+        assert!(matches!(
+            names_to_pair_tuples(&names),
+            Err(IdentError::PartCountError { expected: 2, actual: 1 })
+        ));
+    }
+
+    #[test]
+    fn grant() {
+        let object = PgName::new_ns(vec!["public", "users"]).unwrap();
+        let roles = vec![PgIdent::new("App Role").unwrap()];
+        assert_eq!(grant_statement(&["SELECT"], &object, &roles).unwrap(), "GRANT SELECT ON public.users TO \"App Role\"");
+
+        let object = PgName::new("Orders").unwrap();
+        let roles = vec![PgIdent::new("admin").unwrap(), PgIdent::new("Bob \"B\"").unwrap()];
+        assert_eq!(
+            grant_statement(&["SELECT", "INSERT"], &object, &roles).unwrap(),
+            "GRANT SELECT, INSERT ON \"Orders\" TO admin, \"Bob \"\"B\"\"\""
+        );
+        assert_eq!(revoke_statement(&["ALL"], &object, &roles[..1]).unwrap(), "REVOKE ALL ON \"Orders\" FROM admin");
+
+        assert!(matches!(grant_statement(&["SELECT"], &object, &[]), Err(IdentError::EmptyListError("role"))));
+        assert!(matches!(grant_statement(&[], &object, &roles), Err(IdentError::EmptyListError("privilege"))));
+        assert!(matches!(revoke_statement(&["ALL"], &object, &[]), Err(IdentError::EmptyListError("role"))));
+    }
+
+    #[test]
+    fn copy() {
+        let name = PgName::new_ns(vec!["public", "users"]).unwrap();
+        let columns = vec![PgIdent::new("id").unwrap(), PgIdent::new("Full Name").unwrap(), PgIdent::new("user").unwrap()];
+        assert_eq!(copy_statement(&name, &columns, true), "COPY public.users (id, \"Full Name\", \"user\") TO STDOUT");
+        assert_eq!(copy_statement(&name, &[], false), "COPY public.users FROM STDIN");
+    }
+
+    #[test]
+    fn rename() {
+        let from = PgName::new_ns(vec!["public", "users"]).unwrap();
+        assert_eq!(rename_statement("TABLE", &from, &PgIdent::new("customers").unwrap()), "ALTER TABLE public.users RENAME TO customers");
+        assert_eq!(rename_statement("VIEW", &from, &PgIdent::new("Old Users").unwrap()), "ALTER VIEW public.users RENAME TO \"Old Users\"");
+    }
+
+}
@@ -0,0 +1,97 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{IdentError, PgIdent};
+use crate::util::{is_ident_compatible, quote};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuotePreference {
+    // Quote only when needed, the same as `PgIdent`'s own `Display`
+    #[default]
+    Auto,
+    Always,
+    // Never quote, only allowed for identifiers that are valid bare
+    Never,
+}
+
+// A `PgIdent` with an explicit quoting preference, so a name re-renders the same way
+// regardless of how it was constructed.
+#[derive(Debug)]
+pub struct PreferredIdent<T> where T: AsRef<str> {
+    ident: PgIdent<T>,
+    preference: QuotePreference,
+}
+
+impl<T: AsRef<str>> PreferredIdent<T> {
+    pub fn new(ident: PgIdent<T>, preference: QuotePreference) -> Result<Self, IdentError> {
+        let mut preferred = Self { ident, preference: QuotePreference::Auto };
+        preferred.set_preference(preference)?;
+        Ok(preferred)
+    }
+
+    pub fn set_preference(&mut self, preference: QuotePreference) -> Result<(), IdentError> {
+        if preference == QuotePreference::Never && !is_ident_compatible(&self.ident.as_value()) {
+            return Err(IdentError::QuotingRequiredError());
+        }
+        self.preference = preference;
+        Ok(())
+    }
+
+    pub fn preference(&self) -> QuotePreference {
+        self.preference
+    }
+
+    pub fn ident(&self) -> &PgIdent<T> {
+        &self.ident
+    }
+
+    pub fn into_ident(self) -> PgIdent<T> {
+        self.ident
+    }
+}
+
+impl<T: AsRef<str>> Display for PreferredIdent<T> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self.preference {
+            QuotePreference::Auto => write!(f, "{}", self.ident),
+            QuotePreference::Always => write!(f, "{}", quote(&self.ident.as_value())),
+            QuotePreference::Never => write!(f, "{}", self.ident.as_value()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto() {
+        let id = PreferredIdent::new(PgIdent::new("foo").unwrap(), QuotePreference::Auto).unwrap();
+        assert_eq!(format!("{}", id), "foo");
+        let id = PreferredIdent::new(PgIdent::new("Foo").unwrap(), QuotePreference::default()).unwrap();
+        assert_eq!(format!("{}", id), "\"Foo\"");
+    }
+
+    #[test]
+    fn always() {
+        let id = PreferredIdent::new(PgIdent::new("foo").unwrap(), QuotePreference::Always).unwrap();
+        assert_eq!(format!("{}", id), "\"foo\"");
+        let id = PreferredIdent::new(PgIdent::new("a\"b").unwrap(), QuotePreference::Always).unwrap();
+        assert_eq!(format!("{}", id), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn never() {
+        let quoted: PgIdent<&str> = PgIdent::Quoted("foo".to_string());
+        let id = PreferredIdent::new(quoted, QuotePreference::Never).unwrap();
+        assert_eq!(format!("{}", id), "foo");
+
+        let result = PreferredIdent::new(PgIdent::new("Foo").unwrap(), QuotePreference::Never);
+        assert!(matches!(result, Err(IdentError::QuotingRequiredError())));
+
+        let mut id = PreferredIdent::new(PgIdent::new("My Table").unwrap(), QuotePreference::Always).unwrap();
+        assert!(id.set_preference(QuotePreference::Never).is_err());
+        assert_eq!(id.preference(), QuotePreference::Always);
+    }
+
+}
@@ -0,0 +1,103 @@
+use crate::{PgIdent, PgName};
+use crate::util::{quote, quote_literal};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuoteStyle {
+    // Standard SQL `"name"` quoting
+    #[default]
+    Double,
+    // `'name'` with doubled single quotes, for client protocols that expect literal-style names
+    Single,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RenderOptions {
+    pub quote_style: QuoteStyle,
+    // Writes names containing non-ASCII characters in the SQL standard `U&"..."` form, with
+    // `\XXXX` (or `\+XXXXXX`) escapes, so the output is plain ASCII
+    pub unicode_escapes: bool,
+}
+
+// Quotes `s` for a `U&` prefix, where backslash is the escape character
+fn quote_unicode_escaped(s: &str, quote_char: char) -> String {
+    let mut quoted = format!("U&{}", quote_char);
+    for c in s.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            c if c == quote_char => {
+                quoted.push(c);
+                quoted.push(c);
+            }
+            c if c.is_ascii() => quoted.push(c),
+            c if (c as u32) <= 0xFFFF => quoted.push_str(&format!("\\{:04X}", c as u32)),
+            c => quoted.push_str(&format!("\\+{:06X}", c as u32)),
+        }
+    }
+    quoted.push(quote_char);
+    quoted
+}
+
+impl<T: AsRef<str>> PgIdent<T> {
+    pub fn render_with(&self, options: &RenderOptions) -> String {
+        // Once quoted, a bare name has to be written as Postgres would have folded it
+        let value = self.folded();
+        if options.unicode_escapes && !value.is_ascii() {
+            return match options.quote_style {
+                QuoteStyle::Double => quote_unicode_escaped(&value, '"'),
+                QuoteStyle::Single => quote_unicode_escaped(&value, '\''),
+            };
+        }
+        match self {
+            Self::Id(id) => id.as_ref().to_string(),
+            Self::Quoted(_) => match options.quote_style {
+                QuoteStyle::Double => quote(&self.as_value()),
+                QuoteStyle::Single => quote_literal(&self.as_value()),
+            },
+        }
+    }
+}
+
+impl<T: AsRef<str>> PgName<T> {
+    pub fn render_with(&self, options: &RenderOptions) -> String {
+        self.parts()
+            .iter()
+            .map(|id| id.render_with(options))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_matches_display() {
+        let id = PgName::new_ns(vec!["public", "My \"Table\""]).unwrap();
+        assert_eq!(id.render_with(&RenderOptions::default()), id.to_string());
+    }
+
+    #[test]
+    fn single_quote_style() {
+        let options = RenderOptions { quote_style: QuoteStyle::Single, ..Default::default() };
+        let id = PgName::new("My 'Table'").unwrap();
+        assert_eq!(id.render_with(&options), "'My ''Table'''");
+        let id = PgName::new_ns(vec!["public", "a\"b"]).unwrap();
+        assert_eq!(id.render_with(&options), "public.'a\"b'");
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        let options = RenderOptions { unicode_escapes: true, ..Default::default() };
+        assert_eq!(PgIdent::new("café").unwrap().render_with(&options), "U&\"caf\\00E9\"");
+        assert_eq!(PgIdent::new("Ä \\ \"😀\"").unwrap().render_with(&options), "U&\"\\00C4 \\\\ \"\"\\+01F600\"\"\"");
+        assert_eq!(PgIdent::new("users").unwrap().render_with(&options), "users");
+        assert_eq!(PgIdent::new("My Table").unwrap().render_with(&options), "\"My Table\"");
+        let id: PgIdent<&str> = PgIdent::Id("CAFÉ");
+        assert_eq!(id.render_with(&options), "U&\"caf\\00C9\"");
+        let options = RenderOptions { quote_style: QuoteStyle::Single, unicode_escapes: true };
+        assert_eq!(PgName::new_ns(vec!["public", "café"]).unwrap().render_with(&options), "public.U&'caf\\00E9'");
+    }
+
+}
@@ -0,0 +1,217 @@
+use std::ops::Range;
+
+use crate::PgIdent;
+use crate::keywords::is_reserved;
+use crate::util::{fold, is_ident_char, is_ident_start};
+
+// A small tokenizer that finds identifier tokens in a larger SQL string.
+// It skips string literals, dollar-quoted strings and comments, but is not a parser:
+// reserved key words such as `SELECT` are skipped, since they can't be bare identifiers, but
+// unreserved ones are returned. Bare tokens are returned exactly as written (without case folding).
+pub fn scan_identifiers(sql: &str) -> impl Iterator<Item = (Range<usize>, PgIdent<&str>)> {
+    Scanner { sql, pos: 0 }
+}
+
+struct Scanner<'a> {
+    sql: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn rest(&self) -> &'a str {
+        &self.sql[self.pos..]
+    }
+
+    fn skip_while<F: Fn(char) -> bool>(&mut self, f: F) {
+        let rest = self.rest();
+        self.pos += rest.find(|c| !f(c)).unwrap_or(rest.len());
+    }
+
+    fn skip_past(&mut self, needle: &str) {
+        let rest = self.rest();
+        self.pos += rest.find(needle).map(|i| i + needle.len()).unwrap_or(rest.len());
+    }
+
+    fn skip_block_comment(&mut self) {
+        // Postgres block comments nest
+        let mut depth = 0;
+        while self.pos < self.sql.len() {
+            let rest = self.rest();
+            if rest.starts_with("/*") {
+                depth += 1;
+                self.pos += 2;
+            } else if rest.starts_with("*/") {
+                depth -= 1;
+                self.pos += 2;
+                if depth == 0 {
+                    return;
+                }
+            } else {
+                self.pos += rest.chars().next().unwrap().len_utf8();
+            }
+        }
+    }
+
+    // Skips a single-quoted literal, self.pos must be on the opening quote
+    fn skip_string(&mut self, backslash_escapes: bool) {
+        self.pos += 1;
+        let bytes = self.sql.as_bytes();
+        while self.pos < bytes.len() {
+            match bytes[self.pos] {
+                b'\\' if backslash_escapes => self.pos += 2,
+                b'\'' if bytes.get(self.pos + 1) == Some(&b'\'') => self.pos += 2,
+                b'\'' => {
+                    self.pos += 1;
+                    return;
+                }
+                _ => self.pos += 1,
+            }
+        }
+        self.pos = self.pos.min(bytes.len());
+    }
+
+    // Skips a dollar-quoted string if one starts here, otherwise just the `$`
+    fn skip_dollar(&mut self) {
+        let rest = self.rest();
+        let tag_len = rest[1..]
+            .find(|c| !is_ident_char(c) || c == '$')
+            .filter(|&i| rest[1 + i..].starts_with('$'));
+        match tag_len {
+            Some(len) if !rest[1..].starts_with(|c: char| c.is_ascii_digit()) => {
+                let delim = &rest[..len + 2];
+                self.pos += delim.len();
+                self.skip_past(delim);
+            }
+            _ => {
+                self.pos += 1;
+                self.skip_while(|c| c.is_ascii_digit());
+            }
+        }
+    }
+
+    // Returns the end of the quoted identifier starting at self.pos, if it is terminated
+    fn quoted_end(&self) -> Option<usize> {
+        let bytes = self.sql.as_bytes();
+        let mut i = self.pos + 1;
+        while i < bytes.len() {
+            if bytes[i] == b'"' {
+                if bytes.get(i + 1) == Some(&b'"') {
+                    i += 2;
+                    continue;
+                }
+                return Some(i);
+            }
+            i += 1;
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Scanner<'a> {
+    type Item = (Range<usize>, PgIdent<&'a str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.sql.len() {
+            let start = self.pos;
+            let rest = self.rest();
+            let c = rest.chars().next().unwrap();
+            if rest.starts_with("--") {
+                self.skip_past("\n");
+            } else if rest.starts_with("/*") {
+                self.skip_block_comment();
+            } else if c == '\'' {
+                self.skip_string(false);
+            } else if c == '$' {
+                self.skip_dollar();
+            } else if c == '"' {
+                let Some(end) = self.quoted_end() else {
+                    self.pos = self.sql.len();
+                    return None;
+                };
+                self.pos = end + 1;
+                let inner = &self.sql[start + 1..end];
+                // Not valid identifiers, so they're skipped rather than reported
+                if inner.is_empty() || inner.contains('\x00') {
+                    continue;
+                }
+                return Some((start..self.pos, PgIdent::Quoted(inner.to_string())));
+            } else if is_ident_start(c) {
+                self.skip_while(is_ident_char);
+                let word = &self.sql[start..self.pos];
+                if self.rest().starts_with('\'') && word.len() == 1 {
+                    // String literal prefixes: E'..', B'..', X'..', N'..'
+                    match word {
+                        "e" | "E" => {
+                            self.skip_string(true);
+                            continue;
+                        }
+                        "b" | "B" | "x" | "X" | "n" | "N" => {
+                            self.skip_string(false);
+                            continue;
+                        }
+                        _ => {}
+                    }
+                }
+                if is_reserved(&fold(word)) {
+                    continue;
+                }
+                return Some((start..self.pos, PgIdent::Id(word)));
+            } else if c.is_ascii_digit() {
+                // Numeric literals, including forms like 1.5e10
+                self.skip_while(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_');
+            } else {
+                self.pos += c.len_utf8();
+            }
+        }
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(sql: &str) -> Vec<(Range<usize>, String)> {
+        scan_identifiers(sql).map(|(r, id)| (r, id.to_string())).collect()
+    }
+
+    #[test]
+    fn simple_select() {
+        let found: Vec<_> = scan_identifiers("SELECT \"a\", b FROM t").collect();
+        assert_eq!(found.len(), 3);
+        assert!(matches!(found[0].1, PgIdent::Quoted(_)));
+        assert!(matches!(found[1].1, PgIdent::Id("b")));
+        assert_eq!(
+            tokens("SELECT \"a\", b FROM t"),
+            vec![
+                (7..10, "\"a\"".to_string()),
+                (12..13, "b".to_string()),
+                (19..20, "t".to_string()),
+            ]
+        );
+        // Unreserved key words can be bare identifiers
+        assert_eq!(tokens("select name from \"select\""), vec![(7..11, "name".to_string()), (17..25, "\"select\"".to_string())]);
+    }
+
+    #[test]
+    fn skips_literals_and_comments() {
+        let sql = "select 'it''s' -- c\n, E'\\'x', $tag$ y $tag$, /* a /* b */ c */ 1.5e3, $1 from \"my \"\"t\"\"\"";
+        assert_eq!(
+            tokens(sql),
+            vec![(78..88, "\"my \"\"t\"\"\"".to_string())]
+        );
+    }
+
+    #[test]
+    fn empty_quoted() {
+        assert_eq!(tokens("select \"\""), vec![]);
+        assert_eq!(tokens("\"\" a"), vec![(3..4, "a".to_string())]);
+    }
+
+    #[test]
+    fn unterminated_quote() {
+        assert_eq!(tokens("a \"b"), vec![(0..1, "a".to_string())]);
+    }
+
+}
@@ -0,0 +1,75 @@
+use crate::{IdentError, PgIdent};
+use crate::parse::Parser;
+
+const USER_PLACEHOLDER: &str = "$user";
+
+// Validates the value of a `SET search_path = ...` assignment, and re-renders it in a canonical
+// form: entries separated by `, `, quoted only when needed, and `$user` written as `"$user"`.
+// Anything other than a comma-separated list of schema names is rejected.
+pub fn normalize_search_path(s: &str) -> Result<String, IdentError> {
+    let mut parser = Parser::new(s);
+    let mut entries = Vec::new();
+    loop {
+        parser.skip_whitespace();
+        let value = if parser.eat_word(USER_PLACEHOLDER) {
+            USER_PLACEHOLDER.to_string()
+        } else {
+            parser.ident()?.as_value().into_owned()
+        };
+        if value == USER_PLACEHOLDER {
+            entries.push(format!("\"{}\"", USER_PLACEHOLDER));
+        } else {
+            entries.push(PgIdent::new(value)?.to_string());
+        }
+        parser.skip_whitespace();
+        if parser.is_at_end() {
+            break;
+        }
+        if !parser.eat(',') {
+            return Err(parser.unexpected());
+        }
+    }
+    Ok(entries.join(", "))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_search_paths() {
+        assert_eq!(normalize_search_path("public").unwrap(), "public");
+        assert_eq!(normalize_search_path("\"$user\",public").unwrap(), "\"$user\", public");
+        assert_eq!(normalize_search_path(" $user ,  PUBLIC, \"public\"").unwrap(), "\"$user\", public, public");
+        assert_eq!(normalize_search_path("\"My Schema\", app").unwrap(), "\"My Schema\", app");
+    }
+
+    #[test]
+    fn injection_attempts() {
+        assert!(matches!(
+            normalize_search_path("public; DROP TABLE users"),
+            Err(IdentError::UnexpectedCharError { ch: ';', offset: 6 })
+        ));
+        assert!(matches!(
+            normalize_search_path("\"public\"; DROP TABLE users; --"),
+            Err(IdentError::UnexpectedCharError { ch: ';', offset: 8 })
+        ));
+        assert!(matches!(
+            normalize_search_path("public, \"x; DROP TABLE users"),
+            Err(IdentError::UnterminatedQuoteError())
+        ));
+        assert!(normalize_search_path("public public").is_err());
+        assert!(normalize_search_path("$username").is_err());
+        assert!(matches!(normalize_search_path("public, user"), Err(IdentError::ReservedKeyword { offset: 8, .. })));
+        assert_eq!(normalize_search_path("public, \"user\"").unwrap(), "public, \"user\"");
+    }
+
+    #[test]
+    fn empty_entries() {
+        assert!(matches!(normalize_search_path(""), Err(IdentError::ZeroLengthError())));
+        assert!(matches!(normalize_search_path("public,"), Err(IdentError::ZeroLengthError())));
+        assert!(normalize_search_path("public,,app").is_err());
+    }
+
+}
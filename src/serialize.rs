@@ -0,0 +1,119 @@
+use std::fmt::Formatter;
+
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{PgIdent, PgName};
+
+// Identifiers are serialized as the unquoted value Postgres uses for them (so a bare `Users`
+// is `users`) rather than the `Display` form, and deserialized through `new`/`new_ns`, so
+// invalid names are rejected by the usual checks and the same object is named either way.
+// A single-part name is a plain string, longer names are an array of parts,
+// e.g. `["public", "users"]`.
+
+impl<T: AsRef<str>> Serialize for PgIdent<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.folded())
+    }
+}
+
+impl<'de> Deserialize<'de> for PgIdent<String> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        PgIdent::new(value).map_err(D::Error::custom)
+    }
+}
+
+impl<T: AsRef<str>> Serialize for PgName<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let PgName::Id(id) = self {
+            return id.serialize(serializer);
+        }
+        let parts = self.parts();
+        let mut seq = serializer.serialize_seq(Some(parts.len()))?;
+        for part in parts {
+            seq.serialize_element(part)?;
+        }
+        seq.end()
+    }
+}
+
+struct NameVisitor;
+
+impl<'de> Visitor<'de> for NameVisitor {
+    type Value = PgName<String>;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "an identifier or an array of identifiers")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        PgName::new(value.to_string()).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut parts: Vec<String> = Vec::new();
+        while let Some(part) = seq.next_element()? {
+            parts.push(part);
+        }
+        PgName::new_ns(parts).map_err(A::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for PgName<String> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(NameVisitor)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ident_round_trip() {
+        for value in ["users", "Users", "my \"table\"", "select"] {
+            let id = PgIdent::new(value.to_string()).unwrap();
+            let json = serde_json::to_string(&id).unwrap();
+            assert_eq!(json, serde_json::to_string(value).unwrap());
+            let back: PgIdent<String> = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.to_string(), id.to_string());
+        }
+    }
+
+    #[test]
+    fn name_round_trip() {
+        let id = PgName::new_ns(vec!["public", "Users"]).unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#"["public","Users"]"#);
+        let back: PgName<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.to_string(), "public.\"Users\"");
+
+        let id = PgName::new("users").unwrap();
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""users""#);
+        let back: PgName<String> = serde_json::from_str(r#""users""#).unwrap();
+        assert!(matches!(back, PgName::Id(_)));
+    }
+
+    #[test]
+    fn bare_mixed_case() {
+        // Not something `new` produces, but a bare `Users` names the `users` object
+        let id: PgIdent<&str> = PgIdent::Id("Users");
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#""users""#);
+        let back: PgIdent<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.to_string(), "users");
+        assert!(back.eq_ignore_quoting(&id));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(serde_json::from_str::<PgIdent<String>>(r#""""#).is_err());
+        assert!(serde_json::from_str::<PgName<String>>(r#"["public", "a\u0000"]"#).is_err());
+        assert!(serde_json::from_str::<PgName<String>>("[]").is_err());
+        assert!(serde_json::from_str::<PgName<String>>("1").is_err());
+    }
+
+}
@@ -0,0 +1,53 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{PgIdent, PgName};
+
+// A constraint, trigger or other object named within a table, e.g. `users_pkey` on `app.users`.
+// `Display` gives `app.users.users_pkey` for logs, which isn't valid SQL: statements such as
+// `ALTER TABLE ... DROP CONSTRAINT` take the table and the sub-object name separately.
+#[derive(Debug)]
+pub struct SubObjectName<T> where T: AsRef<str> {
+    pub table: PgName<T>,
+    pub name: PgIdent<T>,
+}
+
+impl<T: AsRef<str>> SubObjectName<T> {
+    pub fn new(table: PgName<T>, name: PgIdent<T>) -> Self {
+        Self { table, name }
+    }
+
+    // Just the quoted sub-object name, for use where the table is already given, e.g.
+    // `DROP TRIGGER <name> ON <table>`
+    pub fn to_sql(&self) -> String {
+        self.name.to_string()
+    }
+}
+
+impl<T: AsRef<str>> Display for SubObjectName<T> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}.{}", self.table, self.name)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_form() {
+        let id = SubObjectName::new(PgName::new_ns(vec!["app", "users"]).unwrap(), PgIdent::new("users_pkey").unwrap());
+        assert_eq!(id.to_string(), "app.users.users_pkey");
+        let id = SubObjectName::new(PgName::new("Users").unwrap(), PgIdent::new("Check Age").unwrap());
+        assert_eq!(id.to_string(), "\"Users\".\"Check Age\"");
+    }
+
+    #[test]
+    fn sql_form() {
+        let id = SubObjectName::new(PgName::new_ns(vec!["app", "users"]).unwrap(), PgIdent::new("users_pkey").unwrap());
+        assert_eq!(id.to_sql(), "users_pkey");
+        let id = SubObjectName::new(PgName::new_ns(vec!["app", "users"]).unwrap(), PgIdent::new("Audit Trigger").unwrap());
+        assert_eq!(format!("DROP TRIGGER {} ON {}", id.to_sql(), id.table), "DROP TRIGGER \"Audit Trigger\" ON app.users");
+    }
+
+}
@@ -0,0 +1,84 @@
+use std::fmt::{Display, Formatter};
+
+use crate::PgName;
+use crate::util::quote_literal;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subscript {
+    // Array element, rendered as `[n]`
+    Index(i64),
+    // JSON object key, rendered as `->'key'`
+    Key(String),
+}
+
+// A column reference followed by array or JSON subscripts, e.g. `arr[1]` or `data->'key'`.
+// The name part is quoted as usual, and string keys are rendered as escaped literals.
+#[derive(Debug)]
+pub struct SubscriptedName<T> where T: AsRef<str> {
+    pub name: PgName<T>,
+    pub subscripts: Vec<Subscript>,
+}
+
+impl<T: AsRef<str>> SubscriptedName<T> {
+    pub fn new(name: PgName<T>) -> Self {
+        Self { name, subscripts: Vec::new() }
+    }
+
+    pub fn index(mut self, index: i64) -> Self {
+        self.subscripts.push(Subscript::Index(index));
+        self
+    }
+
+    pub fn key<K: Into<String>>(mut self, key: K) -> Self {
+        self.subscripts.push(Subscript::Key(key.into()));
+        self
+    }
+}
+
+impl Display for Subscript {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "[{}]", index),
+            Self::Key(key) => write!(f, "->{}", quote_literal(key)),
+        }
+    }
+}
+
+impl<T: AsRef<str>> Display for SubscriptedName<T> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        let mut rendered = self.name.to_string();
+        let mut after_key = false;
+        for subscript in &self.subscripts {
+            // `->` binds more loosely than `[]`, so an index applied to a key lookup needs parens
+            if after_key && matches!(subscript, Subscript::Index(_)) {
+                rendered = format!("({})", rendered);
+            }
+            after_key = matches!(subscript, Subscript::Key(_));
+            rendered.push_str(&subscript.to_string());
+        }
+        write!(f, "{}", rendered)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_subscript() {
+        let id = SubscriptedName::new(PgName::new("arr").unwrap()).index(1);
+        assert_eq!(format!("{}", id), "arr[1]");
+        let id = SubscriptedName::new(PgName::new_ns(vec!["t", "My Arr"]).unwrap()).index(1).index(-2);
+        assert_eq!(format!("{}", id), "t.\"My Arr\"[1][-2]");
+    }
+
+    #[test]
+    fn key_subscript() {
+        let id = SubscriptedName::new(PgName::new("data").unwrap()).key("key");
+        assert_eq!(format!("{}", id), "data->'key'");
+        let id = SubscriptedName::new(PgName::new("data").unwrap()).key("it's").index(0);
+        assert_eq!(format!("{}", id), "(data->'it''s')[0]");
+    }
+
+}
@@ -1,11 +1,17 @@
+use std::borrow::Cow;
+
+use crate::keywords::is_reserved;
+
+// NAMEDATALEN - 1 in a default Postgres build
+pub const MAX_IDENT_BYTES: usize = 63;
 
 pub fn is_ident_compatible(id: &str) -> bool {
     // Rules taken from: https://www.postgresql.org/docs/16/sql-syntax-lexical.html#SQL-SYNTAX-IDENTIFIERS
     // "The system uses no more than NAMEDATALEN-1 bytes of an identifier; longer names can be written
     // in commands, but they will be truncated. By default, NAMEDATALEN is 64 so the maximum identifier
     // length is 63 bytes."
-    // As a practical balance, we'll assume 63 bytes default is kept, and disallow longer
-    if id.len() > 63 || id.len() == 0 {
+    // Over-long names aren't a quoting problem, so length is checked separately by `PgIdent::new`
+    if id.is_empty() {
         return false;
     }
 
@@ -26,5 +32,92 @@ pub fn is_ident_compatible(id: &str) -> bool {
     }
     // Subsequent characters in an identifier or key word can be 
     // letters, underscores, digits (0-9), or dollar signs ($).
-    char_it.all(|c| c.is_lowercase() || c.is_numeric() || c == '_' || c == '$')
-}
\ No newline at end of file
+    if !char_it.all(|c| c.is_lowercase() || c.is_numeric() || c == '_' || c == '$') {
+        return false;
+    }
+    // Reserved key words must be quoted even though they're otherwise valid identifiers.
+    // Non-reserved key words (like `name` or `type`) are allowed bare for table and column names.
+    !is_reserved(id)
+}
+
+// Characters accepted at the start of, and within, a bare identifier token when reading SQL.
+// This is wider than `is_ident_compatible`, as upper-case letters are accepted (and folded),
+// but narrower than the Postgres lexer, which accepts any non-ASCII character.
+pub fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+pub fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+// Equivalent of pg_mbcliplen: the longest prefix of `s` that is at most `len` bytes
+// and ends on a character boundary.
+pub fn clip(s: &str, len: usize) -> &str {
+    let mut len = len.min(s.len());
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+    &s[..len]
+}
+
+pub fn escape(id: &str) -> String {
+    id.replace('"', "\"\"")
+}
+
+pub fn unescape(quoted: &str) -> Cow<'_, str> {
+    if quoted.contains('"') {
+        Cow::Owned(quoted.replace("\"\"", "\""))
+    } else {
+        Cow::Borrowed(quoted)
+    }
+}
+
+pub fn quote(id: &str) -> String {
+    format!("\"{}\"", escape(id))
+}
+
+pub fn escape_literal(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+pub fn quote_literal(value: &str) -> String {
+    format!("'{}'", escape_literal(value))
+}
+
+// Minimal escaping for the contents of a JSON string
+pub fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn fold(id: &str) -> Cow<'_, str> {
+    // Postgres only downcases ASCII letters in unquoted identifiers when using
+    // multi-byte encodings, so we do the same rather than guessing at unicode rules.
+    if id.bytes().any(|b| b.is_ascii_uppercase()) {
+        Cow::Owned(id.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(id)
+    }
+}
+
+// 64-bit FNV-1a, which is stable across platforms and releases, unlike std's hashers
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
@@ -0,0 +1,62 @@
+use std::fmt::{self, Write};
+
+use crate::{IdentError, PgIdent};
+
+// Wraps a `fmt::Write` so SQL can be built up piece by piece, with identifier pieces
+// validated and quoted as they're written.
+pub struct IdentWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> IdentWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    pub fn write_ident(&mut self, s: &str) -> Result<(), IdentError> {
+        let id = PgIdent::new(s)?;
+        write!(self.inner, "{}", id)?;
+        Ok(())
+    }
+
+    pub fn write_raw(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_str(s)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for IdentWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_raw(s)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_query() {
+        let mut writer = IdentWriter::new(String::new());
+        writer.write_raw("SELECT ").unwrap();
+        writer.write_ident("id").unwrap();
+        writer.write_raw(", ").unwrap();
+        writer.write_ident("Full Name").unwrap();
+        write!(writer, " FROM ").unwrap();
+        writer.write_ident("users").unwrap();
+        assert_eq!(writer.into_inner(), "SELECT id, \"Full Name\" FROM users");
+    }
+
+    #[test]
+    fn invalid_ident() {
+        let mut writer = IdentWriter::new(String::new());
+        writer.write_raw("SELECT * FROM ").unwrap();
+        assert!(matches!(writer.write_ident("a\x00b"), Err(IdentError::NullByteError())));
+        assert_eq!(writer.into_inner(), "SELECT * FROM ");
+    }
+
+}